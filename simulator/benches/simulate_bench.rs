@@ -0,0 +1,28 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use virality_sim::{simulate, Simulator, SimulatorInput};
+
+fn sample_input(i: usize) -> SimulatorInput {
+    let mut input = SimulatorInput::default();
+    input.text = format!("Breaking: thread #{} on why this changes everything!", i);
+    input.followers = 10_000 + i as u64;
+    input
+}
+
+fn bench_single_post(c: &mut Criterion) {
+    let input = sample_input(0);
+    c.bench_function("simulate_single_post", |b| {
+        b.iter(|| simulate(black_box(&input)));
+    });
+}
+
+fn bench_batch_throughput(c: &mut Criterion) {
+    let simulator = Simulator::new(virality_sim::config::ScoringConfig::default());
+    let inputs: Vec<SimulatorInput> = (0..500).map(sample_input).collect();
+
+    c.bench_function("simulate_many_500_posts", |b| {
+        b.iter(|| simulator.simulate_many(black_box(&inputs)));
+    });
+}
+
+criterion_group!(benches, bench_single_post, bench_batch_throughput);
+criterion_main!(benches);
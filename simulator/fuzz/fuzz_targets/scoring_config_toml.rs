@@ -0,0 +1,13 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use virality_sim::config::ScoringConfig;
+
+// Malformed or adversarial TOML must fail to parse with an `Err`, never
+// panic or hang -- `ScoringConfig` is loaded from operator-editable files on
+// every server start and config-watcher reload.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        let _ = toml::from_str::<ScoringConfig>(text);
+    }
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use std::collections::HashMap;
+use virality_sim::user::UserProfile;
+
+// Mirrors the `serde_json::from_str` call `UserProfileStore::load` makes
+// against the on-disk profiles file -- malformed JSON must fail with an
+// `Err`, never panic or hang.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(text) = std::str::from_utf8(data) {
+        let _ = serde_json::from_str::<HashMap<String, UserProfile>>(text);
+    }
+});
@@ -8,8 +8,16 @@ pub struct ApiSimulationRequest {
     pub text: Option<String>,
     pub request_id: Option<String>,
     pub media: Option<String>,
+    /// Number of items for `media: "carousel"`/`"gallery"`; ignored otherwise.
+    pub media_count: Option<u8>,
+    /// Whether `media: "video"` is a live broadcast rather than a VOD.
+    pub is_live: Option<bool>,
     pub post_id: Option<String>,
     pub author_id: Option<String>,
+    /// Which `SocialPlatform` backend resolves `author_id`/`post_id` against
+    /// live data (e.g. via `/api/platform/profile`). Defaults to `"x"`;
+    /// `"mastodon"` routes to the configured `MastodonClient` instead.
+    pub platform: Option<String>,
     pub is_oon: Option<bool>,
     pub video_duration_seconds: Option<f64>,
     pub has_link: Option<bool>,
@@ -27,6 +35,9 @@ pub struct ApiSimulationRequest {
     pub controversy: Option<f64>,
     pub sentiment: Option<f64>,
     pub use_ai: Option<bool>,
+    /// Past tweet texts to score novelty against by embedding similarity
+    /// instead of the LLM's own guess. Only used when `use_ai` is set.
+    pub corpus: Option<Vec<String>>,
     pub scoring_mode: Option<String>,
     pub phoenix_weight: Option<f64>,
     pub user_id: Option<String>,
@@ -50,6 +61,16 @@ impl ApiSimulationRequest {
             input.media = MediaType::from_str(media)
                 .ok_or_else(|| format!("invalid media type: {}", media))?;
         }
+        if let MediaType::Carousel { count } = &mut input.media {
+            if let Some(media_count) = self.media_count {
+                *count = media_count;
+            }
+        }
+        if let MediaType::Video { is_live } = &mut input.media {
+            if let Some(live) = self.is_live {
+                *is_live = live;
+            }
+        }
 
         if let Some(post_id) = self.post_id.as_ref() {
             input.post_id = Some(post_id.clone());
@@ -135,6 +156,10 @@ pub struct ApiSimulationResponse {
     pub suggestions: Vec<String>,
     pub llm: Option<LlmScore>,
     pub llm_trace: Option<LlmTrace>,
+    /// Row id in the `TraceStore`'s `llm_traces` table, if the AI-scored
+    /// response was persisted. Pass this to `/api/traces/:id/outcome` once
+    /// the tweet's real engagement is known, to feed the calibration loop.
+    pub trace_id: Option<i64>,
     pub warnings: Vec<String>,
 }
 
@@ -162,6 +187,7 @@ impl ApiSimulationResponse {
             suggestions: output.suggestions,
             llm: output.llm,
             llm_trace: output.llm_trace,
+            trace_id: None,
             warnings,
         }
     }
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use crate::calibration::runner::CalibrationSample;
+use crate::config::ScoringConfig;
+use crate::{simulate_with_mode, ScoringMode};
+
+/// Calibration of predicted vs. observed engagement rate within a single
+/// virality tier, so users can see whether e.g. `Breakout`-tier predictions
+/// actually outperform `Steady`-tier ones on their own account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TierCalibration {
+    pub tier: String,
+    pub sample_count: usize,
+    pub observed_rate: f64,
+    pub predicted_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BacktestReport {
+    pub sample_count: usize,
+    pub spearman_correlation: f64,
+    pub engagement_rate_mae: f64,
+    pub tiers: Vec<TierCalibration>,
+}
+
+/// Runs real posts with known outcomes back through the simulator and
+/// reports how well predicted `final_score`/`expected_unique_engagements`
+/// track what actually happened. Lets users validate that the hardcoded
+/// Phoenix-style weights and `OonScorerConfig.multiplier` actually track
+/// reality on their own account before trusting a live
+/// `ApiSimulationResponse`.
+pub struct BacktestRunner {
+    pub samples: Vec<CalibrationSample>,
+}
+
+impl BacktestRunner {
+    pub fn new(samples: Vec<CalibrationSample>) -> Self {
+        Self { samples }
+    }
+
+    pub fn run(&self, config: &ScoringConfig) -> BacktestReport {
+        if self.samples.is_empty() {
+            return BacktestReport::default();
+        }
+
+        let mut predicted_scores = Vec::with_capacity(self.samples.len());
+        let mut actual_rates = Vec::with_capacity(self.samples.len());
+        let mut errors = Vec::with_capacity(self.samples.len());
+        let mut by_tier: BTreeMap<String, (Vec<f64>, Vec<f64>)> = BTreeMap::new();
+
+        for sample in &self.samples {
+            let input = sample.to_input();
+            let output = simulate_with_mode(&input, None, None, ScoringMode::Heuristic, None, config);
+            let actual_rate = sample.engagement_rate();
+
+            predicted_scores.push(output.final_score);
+            actual_rates.push(actual_rate);
+            errors.push((output.unique_engagement_rate - actual_rate).abs());
+
+            let bucket = by_tier.entry(output.tier.label().to_string()).or_default();
+            bucket.0.push(output.unique_engagement_rate);
+            bucket.1.push(actual_rate);
+        }
+
+        let tiers = by_tier
+            .into_iter()
+            .map(|(tier, (predicted, actual))| TierCalibration {
+                tier,
+                sample_count: predicted.len(),
+                observed_rate: mean(&actual),
+                predicted_rate: mean(&predicted),
+            })
+            .collect();
+
+        BacktestReport {
+            sample_count: self.samples.len(),
+            spearman_correlation: spearman(&predicted_scores, &actual_rates),
+            engagement_rate_mae: mean(&errors),
+            tiers,
+        }
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Spearman rank correlation: the Pearson correlation of each series' ranks,
+/// averaging tied values to the mean rank of their group.
+fn spearman(xs: &[f64], ys: &[f64]) -> f64 {
+    if xs.len() < 2 {
+        return 0.0;
+    }
+    pearson(&rank(xs), &rank(ys))
+}
+
+fn rank(values: &[f64]) -> Vec<f64> {
+    let mut indices: Vec<usize> = (0..values.len()).collect();
+    indices.sort_by(|&a, &b| {
+        values[a].partial_cmp(&values[b]).unwrap_or(Ordering::Equal)
+    });
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut i = 0;
+    while i < indices.len() {
+        let mut j = i;
+        while j + 1 < indices.len() && values[indices[j + 1]] == values[indices[i]] {
+            j += 1;
+        }
+        let average_rank = ((i + j) as f64 / 2.0) + 1.0;
+        for &idx in &indices[i..=j] {
+            ranks[idx] = average_rank;
+        }
+        i = j + 1;
+    }
+    ranks
+}
+
+fn pearson(xs: &[f64], ys: &[f64]) -> f64 {
+    let mean_x = mean(xs);
+    let mean_y = mean(ys);
+
+    let mut numerator = 0.0;
+    let mut denom_x = 0.0;
+    let mut denom_y = 0.0;
+    for (x, y) in xs.iter().zip(ys.iter()) {
+        let dx = x - mean_x;
+        let dy = y - mean_y;
+        numerator += dx * dy;
+        denom_x += dx * dx;
+        denom_y += dy * dy;
+    }
+
+    if denom_x <= 0.0 || denom_y <= 0.0 {
+        return 0.0;
+    }
+    numerator / (denom_x.sqrt() * denom_y.sqrt())
+}
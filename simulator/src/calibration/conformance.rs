@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+use crate::config::ScoringConfig;
+use crate::scoring::ScoredCandidate;
+use crate::{build_pipeline, ActionProbs};
+
+const DEFAULT_TOLERANCE: f64 = 1e-6;
+
+/// One golden test case for the `ScoringPipeline`: an `ActionProbs` input
+/// plus the `WeightedScorer`/`AuthorDiversityScorer`/`OonScorer` outputs it's
+/// expected to produce.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConformanceVector {
+    pub post_id: String,
+    pub author_id: String,
+    pub is_oon: bool,
+    pub vqv_duration: Option<f64>,
+    pub actions: ActionProbs,
+    pub expected_weighted_score: f64,
+    pub expected_diversity_multiplier: f64,
+    pub expected_oon_multiplier: f64,
+    pub expected_score: f64,
+}
+
+/// A single field that diverged beyond tolerance on one vector.
+#[derive(Debug, Clone)]
+pub struct ConformanceMismatch {
+    pub post_id: String,
+    pub field: &'static str,
+    pub expected: f64,
+    pub actual: f64,
+    pub difference: f64,
+}
+
+/// Result of running a `ConformanceRunner`: every vector that diverged,
+/// rather than a panic on the first one, so a refactor's full blast radius
+/// shows up in one run.
+#[derive(Debug, Clone, Default)]
+pub struct ConformanceReport {
+    pub total: usize,
+    pub mismatches: Vec<ConformanceMismatch>,
+}
+
+impl ConformanceReport {
+    pub fn is_conformant(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Loads a corpus of `ConformanceVector`s and asserts the `ScoringPipeline`
+/// still reproduces their expected outputs, locking down
+/// `WeightedScorer`/`AuthorDiversityScorer`/`OonScorer` behavior across
+/// refactors. All vectors are scored together in one pipeline batch (as the
+/// real pipeline is always used), so author-repeat vectors see the same
+/// `AuthorDiversityScorer` decay they would in production.
+pub struct ConformanceRunner {
+    vectors: Vec<ConformanceVector>,
+}
+
+impl ConformanceRunner {
+    pub fn new(vectors: Vec<ConformanceVector>) -> Self {
+        Self { vectors }
+    }
+
+    /// Runs with the default `1e-6` tolerance.
+    pub fn run(&self, config: &ScoringConfig) -> ConformanceReport {
+        self.run_with_tolerance(config, DEFAULT_TOLERANCE)
+    }
+
+    pub fn run_with_tolerance(&self, config: &ScoringConfig, tolerance: f64) -> ConformanceReport {
+        if self.vectors.is_empty() {
+            return ConformanceReport::default();
+        }
+
+        let pipeline = build_pipeline(config);
+        let mut candidates: Vec<ScoredCandidate> = self
+            .vectors
+            .iter()
+            .map(|vector| {
+                ScoredCandidate::new(
+                    vector.post_id.clone(),
+                    vector.author_id.clone(),
+                    vector.is_oon,
+                    vector.vqv_duration,
+                    vector.actions.clone(),
+                )
+            })
+            .collect();
+        pipeline.score(&mut candidates);
+
+        let mut mismatches = Vec::new();
+        for vector in &self.vectors {
+            let Some(candidate) = candidates.iter().find(|candidate| candidate.post_id == vector.post_id)
+            else {
+                continue;
+            };
+            check_field(
+                &mut mismatches,
+                &vector.post_id,
+                "weighted_score",
+                vector.expected_weighted_score,
+                candidate.weighted_score,
+                tolerance,
+            );
+            check_field(
+                &mut mismatches,
+                &vector.post_id,
+                "diversity_multiplier",
+                vector.expected_diversity_multiplier,
+                candidate.diversity_multiplier,
+                tolerance,
+            );
+            check_field(
+                &mut mismatches,
+                &vector.post_id,
+                "oon_multiplier",
+                vector.expected_oon_multiplier,
+                candidate.oon_multiplier,
+                tolerance,
+            );
+            check_field(
+                &mut mismatches,
+                &vector.post_id,
+                "score",
+                vector.expected_score,
+                candidate.score,
+                tolerance,
+            );
+        }
+
+        ConformanceReport {
+            total: self.vectors.len(),
+            mismatches,
+        }
+    }
+}
+
+fn check_field(
+    mismatches: &mut Vec<ConformanceMismatch>,
+    post_id: &str,
+    field: &'static str,
+    expected: f64,
+    actual: f64,
+    tolerance: f64,
+) {
+    let difference = (expected - actual).abs();
+    if difference > tolerance {
+        mismatches.push(ConformanceMismatch {
+            post_id: post_id.to_string(),
+            field,
+            expected,
+            actual,
+            difference,
+        });
+    }
+}
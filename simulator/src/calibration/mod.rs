@@ -1,5 +1,13 @@
+pub mod backtest;
+pub mod conformance;
+pub mod personalization;
 pub mod runner;
+pub mod store;
 pub mod tuning;
 
+pub use backtest::{BacktestReport, BacktestRunner, TierCalibration};
+pub use conformance::{ConformanceMismatch, ConformanceReport, ConformanceRunner, ConformanceVector};
+pub use personalization::{PersonalizationReport, PersonalizationTrainer, PersonalizationTrainingConfig};
 pub use runner::{CalibrationMetrics, CalibrationRunner, CalibrationSample};
-pub use tuning::WeightTuner;
+pub use store::{ObservedOutcome, TraceContext, TraceStore};
+pub use tuning::{TuningSchedule, WeightTuner};
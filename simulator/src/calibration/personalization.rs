@@ -0,0 +1,256 @@
+use crate::calibration::runner::CalibrationSample;
+use crate::config::{LlmBlendConfig, ScoringConfig, TierThresholds};
+use crate::{simulate_with_mode, ScoringMode};
+
+struct TrainingRow {
+    features: [f64; 6],
+    target: f64,
+}
+
+/// Tunables for `PersonalizationTrainer::fit`'s batch gradient descent.
+#[derive(Debug, Clone)]
+pub struct PersonalizationTrainingConfig {
+    pub epochs: usize,
+    pub learning_rate: f64,
+    pub validation_split: f64,
+    pub patience: usize,
+}
+
+impl Default for PersonalizationTrainingConfig {
+    fn default() -> Self {
+        Self {
+            epochs: 500,
+            learning_rate: 0.05,
+            validation_split: 0.2,
+            patience: 20,
+        }
+    }
+}
+
+/// Result of `PersonalizationTrainer::fit`: the fitted config sections plus
+/// enough of the fit's own trail to judge whether to trust them.
+#[derive(Debug, Clone)]
+pub struct PersonalizationReport {
+    pub blend: LlmBlendConfig,
+    pub tiers: TierThresholds,
+    pub epochs_run: usize,
+    pub train_loss: f64,
+    pub validation_loss: f64,
+    pub sample_count: usize,
+}
+
+/// Fits `LlmBlendConfig`'s six blend weights against a user's own historical
+/// posts by batch gradient descent, and recalibrates `TierThresholds` to that
+/// user's own score distribution, so scoring afterward reflects what actually
+/// drives *this* account's reach rather than the crate-wide defaults.
+///
+/// The fitted model is `predicted = sigmoid(bias + w . [hook, clarity,
+/// novelty, controversy, sentiment, shareability])`, trained against each
+/// sample's observed `engagement_rate()` by minimizing mean-squared error.
+/// `training_config.validation_split` of the samples (by input order --
+/// calibration corpora are already chronological, so this is a simple
+/// holdout rather than a shuffle) are held out each epoch; training stops
+/// early once validation loss fails to improve for `patience` epochs.
+pub struct PersonalizationTrainer {
+    samples: Vec<CalibrationSample>,
+}
+
+impl PersonalizationTrainer {
+    pub fn new(samples: Vec<CalibrationSample>) -> Self {
+        Self { samples }
+    }
+
+    pub fn fit(
+        &self,
+        scoring_config: &ScoringConfig,
+        training_config: &PersonalizationTrainingConfig,
+    ) -> PersonalizationReport {
+        let rows = self.training_rows(scoring_config);
+        if rows.is_empty() {
+            return PersonalizationReport {
+                blend: scoring_config.llm_blend.clone(),
+                tiers: scoring_config.tier_thresholds.clone(),
+                epochs_run: 0,
+                train_loss: 0.0,
+                validation_loss: 0.0,
+                sample_count: 0,
+            };
+        }
+
+        let holdout = ((rows.len() as f64) * training_config.validation_split.clamp(0.0, 0.9))
+            .round() as usize;
+        let split = rows.len() - holdout.min(rows.len() - 1);
+        let (train_rows, validation_rows) = rows.split_at(split);
+        let validation_rows = if validation_rows.is_empty() {
+            train_rows
+        } else {
+            validation_rows
+        };
+
+        let mut weights = [
+            scoring_config.llm_blend.hook_weight,
+            scoring_config.llm_blend.clarity_weight,
+            scoring_config.llm_blend.novelty_weight,
+            scoring_config.llm_blend.controversy_weight,
+            scoring_config.llm_blend.sentiment_weight,
+            scoring_config.llm_blend.shareability_weight,
+        ];
+        let mut bias = 0.0;
+
+        let mut best_weights = weights;
+        let mut best_validation_loss = mse_loss(&weights, bias, validation_rows);
+        let mut best_train_loss = mse_loss(&weights, bias, train_rows);
+        let mut epochs_since_improvement = 0usize;
+        let mut epochs_run = 0usize;
+
+        for epoch in 0..training_config.epochs {
+            epochs_run = epoch + 1;
+            let (weight_grad, bias_grad) = gradients(&weights, bias, train_rows);
+            for i in 0..weights.len() {
+                weights[i] -= training_config.learning_rate * weight_grad[i];
+            }
+            bias -= training_config.learning_rate * bias_grad;
+
+            let validation_loss = mse_loss(&weights, bias, validation_rows);
+            if validation_loss < best_validation_loss - 1e-9 {
+                best_validation_loss = validation_loss;
+                best_train_loss = mse_loss(&weights, bias, train_rows);
+                best_weights = weights;
+                epochs_since_improvement = 0;
+            } else {
+                epochs_since_improvement += 1;
+                if epochs_since_improvement >= training_config.patience {
+                    break;
+                }
+            }
+        }
+
+        let blend = LlmBlendConfig {
+            hook_weight: best_weights[0],
+            clarity_weight: best_weights[1],
+            novelty_weight: best_weights[2],
+            controversy_weight: best_weights[3],
+            sentiment_weight: best_weights[4],
+            shareability_weight: best_weights[5],
+        };
+
+        PersonalizationReport {
+            tiers: tier_thresholds_from_scores(self.observed_scores(scoring_config)),
+            blend,
+            epochs_run,
+            train_loss: best_train_loss,
+            validation_loss: best_validation_loss,
+            sample_count: rows.len(),
+        }
+    }
+
+    fn training_rows(&self, scoring_config: &ScoringConfig) -> Vec<TrainingRow> {
+        self.samples
+            .iter()
+            .map(|sample| {
+                let input = sample.to_input();
+                let output =
+                    simulate_with_mode(&input, None, None, ScoringMode::Heuristic, None, scoring_config);
+                TrainingRow {
+                    features: [
+                        output.signals.hook,
+                        output.signals.clarity,
+                        output.signals.novelty,
+                        input.controversy,
+                        input.sentiment,
+                        output.signals.shareability,
+                    ],
+                    target: clamp01(sample.engagement_rate()),
+                }
+            })
+            .collect()
+    }
+
+    fn observed_scores(&self, scoring_config: &ScoringConfig) -> Vec<f64> {
+        self.samples
+            .iter()
+            .map(|sample| {
+                let input = sample.to_input();
+                simulate_with_mode(&input, None, None, ScoringMode::Heuristic, None, scoring_config).score
+            })
+            .collect()
+    }
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+fn clamp01(value: f64) -> f64 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.max(0.0).min(1.0)
+    }
+}
+
+fn predict(weights: &[f64; 6], bias: f64, features: &[f64; 6]) -> f64 {
+    let z = bias + weights.iter().zip(features.iter()).map(|(w, x)| w * x).sum::<f64>();
+    sigmoid(z)
+}
+
+fn mse_loss(weights: &[f64; 6], bias: f64, rows: &[TrainingRow]) -> f64 {
+    if rows.is_empty() {
+        return 0.0;
+    }
+    rows.iter()
+        .map(|row| {
+            let error = predict(weights, bias, &row.features) - row.target;
+            error * error
+        })
+        .sum::<f64>()
+        / rows.len() as f64
+}
+
+/// Analytic gradient of mean-squared error through the sigmoid link:
+/// `d/dz (p - y)^2 = 2(p - y) p (1 - p)`, chained into each weight via
+/// `dz/dw_i = x_i` and into the bias via `dz/dbias = 1`.
+fn gradients(weights: &[f64; 6], bias: f64, rows: &[TrainingRow]) -> ([f64; 6], f64) {
+    let mut weight_grad = [0.0; 6];
+    let mut bias_grad = 0.0;
+    if rows.is_empty() {
+        return (weight_grad, bias_grad);
+    }
+
+    for row in rows {
+        let predicted = predict(weights, bias, &row.features);
+        let delta = 2.0 * (predicted - row.target) * predicted * (1.0 - predicted);
+        for i in 0..6 {
+            weight_grad[i] += delta * row.features[i];
+        }
+        bias_grad += delta;
+    }
+
+    let n = rows.len() as f64;
+    for grad in weight_grad.iter_mut() {
+        *grad /= n;
+    }
+    (weight_grad, bias_grad / n)
+}
+
+/// Recalibrates tier cutoffs to this user's own score distribution (50th /
+/// 75th / 90th / 97th percentiles) rather than the crate-wide defaults, so
+/// e.g. "Breakout" reflects a genuinely exceptional post for this account
+/// instead of an arbitrary global bar.
+fn tier_thresholds_from_scores(mut scores: Vec<f64>) -> TierThresholds {
+    if scores.is_empty() {
+        return TierThresholds::default();
+    }
+    scores.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    TierThresholds {
+        moderate: percentile(&scores, 0.50),
+        high: percentile(&scores, 0.75),
+        very_high: percentile(&scores, 0.90),
+        breakout: percentile(&scores, 0.97),
+    }
+}
+
+fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    let rank = (fraction * (sorted.len() - 1) as f64).round() as usize;
+    sorted[rank.min(sorted.len() - 1)]
+}
@@ -1,11 +1,12 @@
 use serde::{Deserialize, Serialize};
 
 use crate::config::ScoringConfig;
+use crate::ids::PostId;
 use crate::{simulate_with_mode, MediaType, ScoringMode, SimulatorInput};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CalibrationSample {
-    pub post_id: String,
+    pub post_id: PostId,
     pub post_text: String,
     pub author_followers: u64,
     pub author_following: Option<u64>,
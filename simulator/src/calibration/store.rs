@@ -0,0 +1,269 @@
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
+use tokio::sync::Mutex;
+
+use crate::calibration::runner::CalibrationSample;
+use crate::ids::PostId;
+use crate::{LlmScore, LlmTrace};
+
+/// A snapshot of the inputs a scored tweet was evaluated against, stored
+/// alongside its `LlmTrace` so `load_calibration_samples` can later rebuild a
+/// full `CalibrationSample` once an outcome arrives. Mirrors the subset of
+/// `SimulatorInput`/author profile fields `CalibrationSample` needs.
+#[derive(Debug, Clone)]
+pub struct TraceContext {
+    pub post_id: Option<PostId>,
+    pub post_text: String,
+    pub author_followers: u64,
+    pub author_following: Option<u64>,
+    pub account_age_days: Option<u32>,
+    pub avg_engagement_rate: Option<f64>,
+    pub posts_per_day: Option<f64>,
+    pub verified: Option<bool>,
+    pub media_type: String,
+}
+
+/// The engagement a traced tweet actually went on to receive, reported once
+/// it's known (e.g. from a later `CalibrateArgs`/`StreamCalibrateArgs` pull).
+#[derive(Debug, Clone, Default)]
+pub struct ObservedOutcome {
+    pub impressions: u64,
+    pub likes: u64,
+    pub replies: u64,
+    pub reposts: u64,
+    pub quotes: Option<u64>,
+    pub shares: Option<u64>,
+}
+
+/// SQL-backed persistence for `LlmTrace`s and the outcomes later observed
+/// for the tweets they scored, so `WeightTuner` can be re-run on accumulated
+/// real history instead of only an in-memory `Vec<CalibrationSample>` built
+/// from one calibration pull. Every tweet's `LlmScore`/`LlmTrace` is also
+/// kept for its own sake, auditable independent of whether an outcome ever
+/// arrives. Backed by `rusqlite`; all I/O runs on the blocking pool since
+/// `rusqlite::Connection` isn't `Send` across awaits.
+pub struct TraceStore {
+    path: PathBuf,
+    conn: Mutex<Connection>,
+}
+
+impl TraceStore {
+    pub async fn open(path: PathBuf) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|err| format!("failed to create trace store dir: {}", err))?;
+            }
+        }
+        let conn = open_connection(&path)?;
+        Ok(Self {
+            path,
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Records a scored tweet's `LlmScore`/`LlmTrace` plus the input
+    /// snapshot needed to later rebuild a `CalibrationSample`. Returns the
+    /// new row's id, to be passed to `record_outcome` once engagement is
+    /// observed.
+    pub async fn record_trace(
+        &self,
+        context: &TraceContext,
+        score: &LlmScore,
+        trace: &LlmTrace,
+    ) -> Result<i64, String> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT INTO llm_traces (
+                post_id, post_text, author_followers, author_following,
+                account_age_days, avg_engagement_rate, posts_per_day, verified,
+                media_type, model, latency_ms, prompt_summary, prompt,
+                raw_response, prompt_tokens, completion_tokens, total_tokens,
+                output_mode, hook, clarity, novelty, shareability, controversy,
+                sentiment, suggestions
+            ) VALUES (
+                ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14,
+                ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25
+            )",
+            params![
+                context.post_id.as_ref().map(PostId::as_str),
+                context.post_text,
+                context.author_followers as i64,
+                context.author_following.map(|value| value as i64),
+                context.account_age_days,
+                context.avg_engagement_rate,
+                context.posts_per_day,
+                context.verified,
+                context.media_type,
+                trace.model,
+                trace.latency_ms as i64,
+                trace.prompt_summary,
+                trace.prompt,
+                trace.raw_response,
+                trace.prompt_tokens,
+                trace.completion_tokens,
+                trace.total_tokens,
+                trace.output_mode,
+                score.hook,
+                score.clarity,
+                score.novelty,
+                score.shareability,
+                score.controversy,
+                score.sentiment,
+                serde_json::to_string(&score.suggestions).unwrap_or_default(),
+            ],
+        )
+        .map_err(|err| format!("failed to record trace: {}", err))?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Links `trace_id` to the engagement it was later observed to receive.
+    /// Overwrites any outcome already recorded for that trace, so a trace
+    /// can be re-measured (e.g. after more impressions land).
+    pub async fn record_outcome(
+        &self,
+        trace_id: i64,
+        outcome: &ObservedOutcome,
+    ) -> Result<(), String> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT INTO trace_outcomes (
+                trace_id, impressions, likes, replies, reposts, quotes, shares
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            ON CONFLICT(trace_id) DO UPDATE SET
+                impressions = excluded.impressions,
+                likes = excluded.likes,
+                replies = excluded.replies,
+                reposts = excluded.reposts,
+                quotes = excluded.quotes,
+                shares = excluded.shares",
+            params![
+                trace_id,
+                outcome.impressions as i64,
+                outcome.likes as i64,
+                outcome.replies as i64,
+                outcome.reposts as i64,
+                outcome.quotes.map(|value| value as i64),
+                outcome.shares.map(|value| value as i64),
+            ],
+        )
+        .map_err(|err| format!("failed to record outcome: {}", err))?;
+        Ok(())
+    }
+
+    /// Hydrates every trace with a recorded outcome into a `CalibrationSample`,
+    /// ready to pass straight to `WeightTuner::new`. Traces with no outcome
+    /// yet are skipped, not defaulted to zero engagement.
+    pub async fn load_calibration_samples(&self) -> Result<Vec<CalibrationSample>, String> {
+        let conn = self.conn.lock().await;
+        let mut statement = conn
+            .prepare(
+                "SELECT
+                    t.id, t.post_id, t.post_text, t.author_followers, t.author_following,
+                    t.account_age_days, t.avg_engagement_rate, t.posts_per_day, t.verified,
+                    t.media_type, o.impressions, o.likes, o.replies, o.reposts, o.quotes, o.shares
+                FROM llm_traces t
+                JOIN trace_outcomes o ON o.trace_id = t.id
+                ORDER BY t.id",
+            )
+            .map_err(|err| format!("failed to prepare calibration query: {}", err))?;
+
+        let rows = statement
+            .query_map([], |row| {
+                let post_id: Option<String> = row.get(1)?;
+                Ok(CalibrationSample {
+                    post_id: post_id
+                        .and_then(|value| PostId::new(value).ok())
+                        .unwrap_or_else(|| PostId::new(format!("trace-{}", row.get::<_, i64>(0)?)).unwrap()),
+                    post_text: row.get(2)?,
+                    author_followers: row.get::<_, i64>(3)? as u64,
+                    author_following: row.get::<_, Option<i64>>(4)?.map(|value| value as u64),
+                    account_age_days: row.get(5)?,
+                    avg_engagement_rate: row.get(6)?,
+                    posts_per_day: row.get(7)?,
+                    verified: row.get(8)?,
+                    media_type: row.get(9)?,
+                    actual_impressions: row.get::<_, i64>(10)? as u64,
+                    actual_likes: row.get::<_, i64>(11)? as u64,
+                    actual_replies: row.get::<_, i64>(12)? as u64,
+                    actual_reposts: row.get::<_, i64>(13)? as u64,
+                    actual_quotes: row.get::<_, Option<i64>>(14)?.map(|value| value as u64),
+                    actual_shares: row.get::<_, Option<i64>>(15)?.map(|value| value as u64),
+                })
+            })
+            .map_err(|err| format!("failed to read calibration rows: {}", err))?;
+
+        let mut samples = Vec::new();
+        for row in rows {
+            samples.push(row.map_err(|err| format!("failed to decode calibration row: {}", err))?);
+        }
+        Ok(samples)
+    }
+
+    /// Looks up whether `trace_id` already has an outcome recorded, without
+    /// pulling the full calibration sample.
+    pub async fn has_outcome(&self, trace_id: i64) -> Result<bool, String> {
+        let conn = self.conn.lock().await;
+        let exists: Option<i64> = conn
+            .query_row(
+                "SELECT 1 FROM trace_outcomes WHERE trace_id = ?1",
+                params![trace_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|err| format!("failed to check trace outcome: {}", err))?;
+        Ok(exists.is_some())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+fn open_connection(path: &Path) -> Result<Connection, String> {
+    let conn = Connection::open(path)
+        .map_err(|err| format!("failed to open trace store {}: {}", path.display(), err))?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS llm_traces (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            post_id TEXT,
+            post_text TEXT NOT NULL,
+            author_followers INTEGER NOT NULL,
+            author_following INTEGER,
+            account_age_days INTEGER,
+            avg_engagement_rate REAL,
+            posts_per_day REAL,
+            verified INTEGER,
+            media_type TEXT NOT NULL,
+            model TEXT NOT NULL,
+            latency_ms INTEGER NOT NULL,
+            prompt_summary TEXT NOT NULL,
+            prompt TEXT NOT NULL,
+            raw_response TEXT NOT NULL,
+            prompt_tokens INTEGER,
+            completion_tokens INTEGER,
+            total_tokens INTEGER,
+            output_mode TEXT NOT NULL,
+            hook REAL NOT NULL,
+            clarity REAL NOT NULL,
+            novelty REAL NOT NULL,
+            shareability REAL NOT NULL,
+            controversy REAL NOT NULL,
+            sentiment REAL NOT NULL,
+            suggestions TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );
+        CREATE TABLE IF NOT EXISTS trace_outcomes (
+            trace_id INTEGER PRIMARY KEY REFERENCES llm_traces(id),
+            impressions INTEGER NOT NULL,
+            likes INTEGER NOT NULL,
+            replies INTEGER NOT NULL,
+            reposts INTEGER NOT NULL,
+            quotes INTEGER,
+            shares INTEGER,
+            recorded_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+        );",
+    )
+    .map_err(|err| format!("failed to migrate trace store schema: {}", err))?;
+    Ok(conn)
+}
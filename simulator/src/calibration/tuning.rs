@@ -5,6 +5,43 @@ use crate::config::ScoringConfig;
 use crate::scoring::ActionWeights;
 use crate::{simulate_with_mode, ScoringMode};
 
+/// Controls `WeightTuner::tune_with`'s simulated-annealing search, so a
+/// caller can trade runtime for fit quality instead of being stuck with one
+/// fixed iteration count.
+#[derive(Debug, Clone, Copy)]
+pub struct TuningSchedule {
+    /// Annealing steps per restart.
+    pub iterations: usize,
+    /// Starting temperature; higher accepts more worsening moves early on.
+    pub initial_temperature: f64,
+    /// Geometric decay applied to both temperature and perturbation scale
+    /// after every step (e.g. `0.97`).
+    pub cooling: f64,
+    /// Starting multiplicative perturbation step passed to `perturb_weights`.
+    pub initial_scale: f64,
+    /// Number of extra restarts beyond the first run, each reseeding
+    /// `current` from `best` with a large scale bump to escape the basin
+    /// the search has settled into.
+    pub restarts: usize,
+    /// Scale used for a restart's reseeding perturbation (kept separate
+    /// from `initial_scale` since it needs to be large enough to actually
+    /// jump out of a local minimum).
+    pub restart_scale: f64,
+}
+
+impl Default for TuningSchedule {
+    fn default() -> Self {
+        Self {
+            iterations: 200,
+            initial_temperature: 1.0,
+            cooling: 0.97,
+            initial_scale: 0.2,
+            restarts: 4,
+            restart_scale: 0.6,
+        }
+    }
+}
+
 pub struct WeightTuner {
     pub calibration_data: Vec<CalibrationSample>,
 }
@@ -14,20 +51,59 @@ impl WeightTuner {
         Self { calibration_data }
     }
 
+    /// Tunes with `TuningSchedule::default()`. See `tune_with` for control
+    /// over the annealing schedule.
     pub fn tune(&self, initial_weights: ActionWeights, config: &ScoringConfig) -> ActionWeights {
+        self.tune_with(initial_weights, config, TuningSchedule::default())
+    }
+
+    /// Simulated annealing over the RMSE `objective`: always accepts an
+    /// improving candidate, otherwise accepts a worsening one with
+    /// probability `exp(-(cand_err - cur_err) / temperature)`. Temperature
+    /// and perturbation scale both cool geometrically by `schedule.cooling`
+    /// each step, so exploration narrows over time. `best`/`best_score`
+    /// track the best weights seen independent of the wandering `current`
+    /// state, and `schedule.restarts` reseeds `current` from `best` with a
+    /// large scale bump to escape local minima the search has settled into.
+    pub fn tune_with(
+        &self,
+        initial_weights: ActionWeights,
+        config: &ScoringConfig,
+        schedule: TuningSchedule,
+    ) -> ActionWeights {
         let mut rng = StdRng::seed_from_u64(42);
+
         let mut best = initial_weights.clone();
         let mut best_score = objective(&best, &self.calibration_data, config);
+        let mut current = best.clone();
+        let mut current_score = best_score;
+
+        for restart in 0..=schedule.restarts {
+            if restart > 0 {
+                current = perturb_weights(&best, &mut rng, schedule.restart_scale);
+                current_score = objective(&current, &self.calibration_data, config);
+            }
+
+            let mut temperature = schedule.initial_temperature;
+            let mut scale = schedule.initial_scale;
+
+            for _ in 0..schedule.iterations {
+                let candidate = perturb_weights(&current, &mut rng, scale);
+                let candidate_score = objective(&candidate, &self.calibration_data, config);
+                let delta = candidate_score - current_score;
+                let accept = delta < 0.0 || rng.gen::<f64>() < (-delta / temperature.max(1e-9)).exp();
 
-        let iterations = 200;
-        let step = 0.2;
+                if accept {
+                    current = candidate;
+                    current_score = candidate_score;
+                    if current_score < best_score {
+                        best = current.clone();
+                        best_score = current_score;
+                    }
+                }
 
-        for _ in 0..iterations {
-            let candidate = perturb_weights(&best, &mut rng, step);
-            let score = objective(&candidate, &self.calibration_data, config);
-            if score < best_score {
-                best = candidate;
-                best_score = score;
+                temperature *= schedule.cooling;
+                scale *= schedule.cooling;
             }
         }
 
@@ -84,6 +160,7 @@ fn perturb_weights(weights: &ActionWeights, rng: &mut StdRng, scale: f64) -> Act
         block: adjust(weights.block),
         mute: adjust(weights.mute),
         report: adjust(weights.report),
+        hide_post: adjust(weights.hide_post),
         dwell_time: adjust(weights.dwell_time),
     }
 }
@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
 use std::path::{Path, PathBuf};
 
-use crate::scoring::{ActionWeights, AuthorDiversityConfig, OonScorerConfig};
+use crate::lexicon::{default_lexicons, LanguageLexicon};
+use crate::scoring::{ActionWeights, AuthorDiversityConfig, ModerationConfig, OonScorerConfig};
 use crate::ScoringMode;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,47 +66,258 @@ impl Default for WeightedConfig {
     }
 }
 
+/// Weights blending an `LlmScore`'s signals into the heuristic ones in
+/// `simulate_with_mode`. Hand-tuned defaults; see
+/// `calibration::PersonalizationTrainer` for fitting them against a user's
+/// own historical posts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmBlendConfig {
+    pub hook_weight: f64,
+    pub clarity_weight: f64,
+    pub novelty_weight: f64,
+    pub controversy_weight: f64,
+    pub sentiment_weight: f64,
+    pub shareability_weight: f64,
+}
+
+impl Default for LlmBlendConfig {
+    fn default() -> Self {
+        Self {
+            hook_weight: 0.6,
+            clarity_weight: 0.6,
+            novelty_weight: 0.6,
+            controversy_weight: 0.5,
+            sentiment_weight: 0.5,
+            shareability_weight: 0.6,
+        }
+    }
+}
+
+/// Score cutoffs separating `ViralityTier`s. Hand-tuned defaults; see
+/// `calibration::PersonalizationTrainer` for recalibrating them to a user's
+/// own score distribution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TierThresholds {
+    pub moderate: f64,
+    pub high: f64,
+    pub very_high: f64,
+    pub breakout: f64,
+}
+
+impl Default for TierThresholds {
+    fn default() -> Self {
+        Self {
+            moderate: 35.0,
+            high: 55.0,
+            very_high: 75.0,
+            breakout: 90.0,
+        }
+    }
+}
+
+/// Current `ScoringConfig` schema version. Bump this and add an ordered
+/// entry to `MIGRATIONS` whenever a field is added that shouldn't be left
+/// silently at its `serde(default)` value for configs written by an older
+/// binary -- the migration backfills it explicitly and `load` rewrites the
+/// file, so what changed is visible on disk instead of hidden behind a
+/// default.
+pub const CURRENT_CONFIG_VERSION: u32 = 1;
+
+/// One migration step applied while loading an older config, returned by
+/// `load`/`load_with_environment`/`reload_from_path` so callers can log
+/// exactly what changed.
+#[derive(Debug, Clone)]
+pub struct AppliedMigration {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub description: &'static str,
+}
+
+type MigrationFn = fn(&mut toml::Value);
+
+/// Ordered `(from_version, to_version, migrate, description)` steps. Applied
+/// in order starting from a config's on-disk `version` (absent counts as
+/// `0`), so a config several versions behind runs every intervening step.
+const MIGRATIONS: &[(u32, u32, MigrationFn, &str)] = &[(
+    0,
+    1,
+    migrate_0_to_1,
+    "backfilled llm_blend and tier_thresholds defaults introduced in schema v1",
+)];
+
+fn migrate_0_to_1(value: &mut toml::Value) {
+    let Some(table) = value.as_table_mut() else {
+        return;
+    };
+    table
+        .entry("llm_blend")
+        .or_insert_with(|| toml::Value::try_from(LlmBlendConfig::default()).expect("LlmBlendConfig serializes"));
+    table.entry("tier_thresholds").or_insert_with(|| {
+        toml::Value::try_from(TierThresholds::default()).expect("TierThresholds serializes")
+    });
+}
+
+fn run_migrations(value: &mut toml::Value, from_version: u32) -> Vec<AppliedMigration> {
+    let mut applied = Vec::new();
+    let mut version = from_version;
+    for (from, to, migrate, description) in MIGRATIONS {
+        if *from == version {
+            migrate(value);
+            applied.push(AppliedMigration {
+                from_version: *from,
+                to_version: *to,
+                description,
+            });
+            version = *to;
+        }
+    }
+    applied
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScoringConfig {
+    /// Schema version of this file. Missing from configs written before this
+    /// field existed, which `read_from_path` treats as version `0` -- the
+    /// oldest schema `MIGRATIONS` knows how to bring forward.
+    #[serde(default)]
+    pub version: u32,
     pub scoring: ScoringModeConfig,
     pub weights: ActionWeights,
     pub weighted: WeightedConfig,
     pub diversity: AuthorDiversityConfig,
     pub oon: OonScorerConfig,
     pub phoenix: PhoenixConfig,
+    pub moderation: ModerationConfig,
+    /// Hook-word and CTA lexicons keyed by language tag, selected by
+    /// `extract_text_features` once it detects the dominant script of the
+    /// post text. Missing from older config files, so it's defaulted on
+    /// deserialize rather than required.
+    #[serde(default = "default_lexicons")]
+    pub lexicons: HashMap<String, LanguageLexicon>,
+    /// Missing from older config files, so both default on deserialize.
+    #[serde(default)]
+    pub llm_blend: LlmBlendConfig,
+    #[serde(default)]
+    pub tier_thresholds: TierThresholds,
+    /// Named `[environments.<name>]` overlays, each a partial TOML table
+    /// deep-merged onto the rest of this config by `ScoringConfig::load`
+    /// when selected via `SCORING_ENV` (or an explicit
+    /// `load_with_environment` call). Lets operators run parallel ranking
+    /// experiments (e.g. `experiment_diversity_v2`) from one config file
+    /// without duplicating the full weight table.
+    #[serde(default)]
+    pub environments: HashMap<String, toml::Value>,
 }
 
 impl Default for ScoringConfig {
     fn default() -> Self {
         Self {
+            version: CURRENT_CONFIG_VERSION,
             scoring: ScoringModeConfig::default(),
             weights: ActionWeights::default(),
             weighted: WeightedConfig::default(),
             diversity: AuthorDiversityConfig::default(),
             oon: OonScorerConfig::default(),
             phoenix: PhoenixConfig::default(),
+            moderation: ModerationConfig::default(),
+            lexicons: default_lexicons(),
+            llm_blend: LlmBlendConfig::default(),
+            tier_thresholds: TierThresholds::default(),
+            environments: HashMap::new(),
         }
     }
 }
 
 impl ScoringConfig {
-    pub fn load(path: Option<PathBuf>) -> Result<(Self, Option<PathBuf>), String> {
+    pub fn load(
+        path: Option<PathBuf>,
+    ) -> Result<(Self, Option<PathBuf>, Vec<AppliedMigration>), String> {
+        Self::load_with_environment(path, active_environment().as_deref())
+    }
+
+    /// Like `load`, but with an explicit `[environments.<name>]` overlay
+    /// instead of reading it from `SCORING_ENV`. The overlay is deep-merged
+    /// onto the base config before env-var overrides apply, so resolution
+    /// order is base < environment overlay < individual env vars. Fields the
+    /// overlay doesn't set fall back to the base value.
+    pub fn load_with_environment(
+        path: Option<PathBuf>,
+        environment: Option<&str>,
+    ) -> Result<(Self, Option<PathBuf>, Vec<AppliedMigration>), String> {
         let config_path = path.or_else(default_config_path);
-        let mut config = if let Some(path) = config_path.as_ref() {
+        let (mut config, migrations) = if let Some(path) = config_path.as_ref() {
             if path.exists() {
-                let contents = std::fs::read_to_string(path)
-                    .map_err(|err| format!("failed to read config: {}", err))?;
-                toml::from_str(&contents)
-                    .map_err(|err| format!("failed to parse config: {}", err))?
+                let (config, migrations) = Self::read_from_path(path, environment)?;
+                if !migrations.is_empty() {
+                    config.write(path)?;
+                }
+                (config, migrations)
             } else {
-                ScoringConfig::default()
+                (ScoringConfig::default(), Vec::new())
             }
         } else {
-            ScoringConfig::default()
+            (ScoringConfig::default(), Vec::new())
         };
 
         config.apply_env_overrides();
-        Ok((config, config_path))
+        Ok((config, config_path, migrations))
+    }
+
+    /// Re-reads and re-parses `path` (with the `SCORING_ENV` overlay, if
+    /// any, re-applied), applying env overrides, without the "missing file
+    /// falls back to defaults" leniency `load` allows at startup -- used by
+    /// `config_watcher::ScoringConfigWatcher` to hot-reload a config that's
+    /// already known to exist.
+    pub fn reload_from_path(path: &Path) -> Result<(Self, Vec<AppliedMigration>), String> {
+        let (mut config, migrations) = Self::read_from_path(path, active_environment().as_deref())?;
+        if !migrations.is_empty() {
+            config.write(path)?;
+        }
+        config.apply_env_overrides();
+        Ok((config, migrations))
+    }
+
+    fn read_from_path(
+        path: &Path,
+        environment: Option<&str>,
+    ) -> Result<(Self, Vec<AppliedMigration>), String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| format!("failed to read config: {}", err))?;
+        let mut value: toml::Value =
+            toml::from_str(&contents).map_err(|err| format!("failed to parse config: {}", err))?;
+
+        if let Some(name) = environment {
+            let overlay = value
+                .get("environments")
+                .and_then(|environments| environments.get(name))
+                .cloned();
+            if let Some(overlay) = overlay {
+                merge_toml_value(&mut value, &overlay);
+            }
+        }
+
+        let file_version = value
+            .get("version")
+            .and_then(|version| version.as_integer())
+            .map(|version| version as u32)
+            .unwrap_or(0);
+        if file_version > CURRENT_CONFIG_VERSION {
+            return Err(format!(
+                "config schema version {} is newer than this binary supports (max {})",
+                file_version, CURRENT_CONFIG_VERSION
+            ));
+        }
+        let migrations = run_migrations(&mut value, file_version);
+        if let Some(table) = value.as_table_mut() {
+            table.insert(
+                "version".to_string(),
+                toml::Value::Integer(CURRENT_CONFIG_VERSION as i64),
+            );
+        }
+
+        let config =
+            ScoringConfig::deserialize(value).map_err(|err| format!("failed to parse config: {}", err))?;
+        Ok((config, migrations))
     }
 
     pub fn write(&self, path: &Path) -> Result<(), String> {
@@ -155,3 +368,30 @@ fn default_config_path() -> Option<PathBuf> {
         .map(PathBuf::from)
         .or_else(|| Some(PathBuf::from("config/scoring.toml")))
 }
+
+fn active_environment() -> Option<String> {
+    env::var("SCORING_ENV")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// Recursively merges `overlay` onto `base` in place: tables merge key by
+/// key (overlay wins on conflicts, recursing into nested tables), any other
+/// value in `overlay` replaces `base` outright.
+fn merge_toml_value(base: &mut toml::Value, overlay: &toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base_table), toml::Value::Table(overlay_table)) => {
+            for (key, overlay_value) in overlay_table {
+                match base_table.get_mut(key) {
+                    Some(base_value) => merge_toml_value(base_value, overlay_value),
+                    None => {
+                        base_table.insert(key.clone(), overlay_value.clone());
+                    }
+                }
+            }
+        }
+        (base_value, overlay_value) => {
+            *base_value = overlay_value.clone();
+        }
+    }
+}
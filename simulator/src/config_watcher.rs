@@ -0,0 +1,141 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::{mpsc, watch, RwLock};
+
+use crate::config::ScoringConfig;
+
+/// Debounce window for coalescing the burst of filesystem events a single
+/// save can trigger (editors frequently write-then-rename, firing several
+/// `Modify`/`Create` events for what is really one logical write).
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Watches a `ScoringConfig` TOML file on disk and hot-swaps it into a
+/// shared `Arc<RwLock<ScoringConfig>>` (e.g. the server's `AppState`) as it
+/// changes, so tuning `ActionWeights`, `AuthorDiversityConfig`,
+/// `OonScorerConfig`, or `ScoringMode` no longer requires a restart.
+///
+/// `notify`'s callback runs on its own thread and isn't async-aware, so
+/// events are forwarded over a `tokio::mpsc` channel into an async task that
+/// does the actual debouncing and reloading. A burst of events collapses
+/// into a single reload once `DEBOUNCE` passes with no further events. A
+/// config that fails to read, parse, or apply env overrides is logged and
+/// discarded -- the previously-loaded config keeps serving rather than the
+/// process crashing or silently going dark.
+///
+/// `generation()` reports how many reloads have actually swapped in a new
+/// config (not how many filesystem events fired), so a consumer caching a
+/// `WeightedScorer`/`ScoringPipeline` built from the old config knows when
+/// to rebuild it; `changed()` resolves the next time that happens.
+pub struct ScoringConfigWatcher {
+    generation: watch::Receiver<u64>,
+    _watcher: RecommendedWatcher,
+}
+
+impl ScoringConfigWatcher {
+    /// Starts watching `config_path` in the background. `config` is assumed
+    /// to already reflect that same path (as `ScoringConfig::load` would
+    /// produce).
+    pub fn spawn(config: Arc<RwLock<ScoringConfig>>, config_path: PathBuf) -> Result<Self, String> {
+        // Watching `config_path` itself doesn't survive an editor's
+        // write-to-tmp-then-rename-over save: the rename replaces the
+        // inode, the kernel fires `IN_IGNORED` for the now-deleted original,
+        // and the watch silently dies with no further events ever
+        // delivered. Watching the parent directory survives renames; events
+        // are filtered down to `config_path` in `run_watch_loop`.
+        let watch_dir = config_path
+            .parent()
+            .map(Path::to_path_buf)
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        let (event_tx, event_rx) = mpsc::channel(64);
+        let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |event| {
+            let _ = event_tx.blocking_send(event);
+        })
+        .map_err(|err| format!("failed to start config watcher: {}", err))?;
+        watcher
+            .watch(&watch_dir, RecursiveMode::NonRecursive)
+            .map_err(|err| format!("failed to watch {}: {}", watch_dir.display(), err))?;
+
+        let (generation_tx, generation_rx) = watch::channel(0u64);
+        tokio::spawn(run_watch_loop(event_rx, config, config_path, generation_tx));
+
+        Ok(Self {
+            generation: generation_rx,
+            _watcher: watcher,
+        })
+    }
+
+    /// Number of reloads that have actually swapped in a new config so far.
+    pub fn generation(&self) -> u64 {
+        *self.generation.borrow()
+    }
+
+    /// Resolves the next time `generation()` advances.
+    pub async fn changed(&mut self) -> Result<(), watch::error::RecvError> {
+        self.generation.changed().await
+    }
+}
+
+async fn run_watch_loop(
+    mut events: mpsc::Receiver<notify::Result<notify::Event>>,
+    config: Arc<RwLock<ScoringConfig>>,
+    config_path: PathBuf,
+    generation: watch::Sender<u64>,
+) {
+    loop {
+        let Some(event) = events.recv().await else {
+            return;
+        };
+        match event {
+            Ok(event)
+                if (event.kind.is_modify() || event.kind.is_create())
+                    && event.paths.iter().any(|path| path == &config_path) => {}
+            Ok(_) => continue,
+            Err(err) => {
+                tracing::warn!(error = %err, "config watcher event error");
+                continue;
+            }
+        }
+
+        // Drain/ignore further events until things go quiet for DEBOUNCE, so a
+        // single save's burst of writes collapses into one reload.
+        loop {
+            match tokio::time::timeout(DEBOUNCE, events.recv()).await {
+                Ok(Some(_)) => continue,
+                Ok(None) => return,
+                Err(_) => break,
+            }
+        }
+
+        reload(&config, &config_path, &generation).await;
+    }
+}
+
+async fn reload(config: &Arc<RwLock<ScoringConfig>>, config_path: &Path, generation: &watch::Sender<u64>) {
+    match ScoringConfig::reload_from_path(config_path) {
+        Ok((new_config, migrations)) => {
+            for migration in &migrations {
+                tracing::info!(
+                    from = migration.from_version,
+                    to = migration.to_version,
+                    description = migration.description,
+                    "migrated scoring config schema"
+                );
+            }
+            *config.write().await = new_config;
+            generation.send_modify(|value| *value += 1);
+            tracing::info!(path = %config_path.display(), "reloaded scoring config");
+        }
+        Err(err) => {
+            tracing::warn!(
+                path = %config_path.display(),
+                error = %err,
+                "failed to reload scoring config, keeping previous config"
+            );
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use reqwest::header::AUTHORIZATION;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+use crate::llm::{clamp01, LlmResult, ScoringBackend};
+
+/// A provider embeddings endpoint (`/v1/embeddings`), used to turn novelty
+/// scoring into a measurement against a corpus of past tweets instead of a
+/// number the LLM invents. Mirrors `llm::from_env`'s conventions: `XAI_API_KEY`
+/// is checked before the generic `OPENAI_API_KEY`, and `EMBEDDINGS_API_BASE`/
+/// `EMBEDDINGS_MODEL` override the endpoint/model independently of the
+/// chat-completions backend's own `*_API_BASE`/`*_MODEL` vars.
+#[derive(Clone)]
+pub struct EmbeddingsClient {
+    client: reqwest::Client,
+    api_key: String,
+    api_base: String,
+    model: String,
+    provider_label: &'static str,
+}
+
+impl EmbeddingsClient {
+    pub fn from_env() -> Option<Self> {
+        if let Ok(api_key) = env::var("XAI_API_KEY") {
+            let api_base = env::var("EMBEDDINGS_API_BASE")
+                .or_else(|_| env::var("XAI_API_BASE"))
+                .unwrap_or_else(|_| "https://api.x.ai/v1".to_string());
+            let model = env::var("EMBEDDINGS_MODEL")
+                .unwrap_or_else(|_| "text-embedding-3-small".to_string());
+            return Some(Self::new(api_key, api_base, model, "xAI"));
+        }
+
+        let api_key = env::var("OPENAI_API_KEY").ok()?;
+        let api_base = env::var("EMBEDDINGS_API_BASE")
+            .or_else(|_| env::var("OPENAI_API_BASE"))
+            .unwrap_or_else(|_| "https://api.openai.com/v1".to_string());
+        let model =
+            env::var("EMBEDDINGS_MODEL").unwrap_or_else(|_| "text-embedding-3-small".to_string());
+        Some(Self::new(api_key, api_base, model, "OpenAI"))
+    }
+
+    pub fn new(
+        api_key: String,
+        api_base: String,
+        model: String,
+        provider_label: &'static str,
+    ) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key,
+            api_base,
+            model,
+            provider_label,
+        }
+    }
+
+    /// Embeds a single piece of text via the provider's `/embeddings` endpoint.
+    pub async fn embed(&self, text: &str) -> Result<Vec<f64>, String> {
+        let url = format!("{}/embeddings", self.api_base.trim_end_matches('/'));
+        let request = EmbeddingsRequest {
+            model: self.model.clone(),
+            input: text.to_string(),
+        };
+
+        let response = self
+            .client
+            .post(url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.api_key))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|err| format!("{} embeddings request failed: {}", self.provider_label, err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            let detail = error_body.trim();
+            if detail.is_empty() {
+                return Err(format!(
+                    "{} embeddings API error: {}",
+                    self.provider_label, status
+                ));
+            }
+            return Err(format!(
+                "{} embeddings API error: {} {}",
+                self.provider_label, status, detail
+            ));
+        }
+
+        let body: EmbeddingsResponse = response.json().await.map_err(|err| {
+            format!(
+                "{} embeddings response parse failed: {}",
+                self.provider_label, err
+            )
+        })?;
+
+        body.data
+            .into_iter()
+            .next()
+            .map(|datum| datum.embedding)
+            .ok_or_else(|| format!("{} embeddings response missing data", self.provider_label))
+    }
+
+    /// Scores `text` with `backend` as usual, then blends in corpus-based
+    /// novelty via `apply_corpus_novelty`.
+    pub async fn score_text_with_corpus(
+        &self,
+        backend: &dyn ScoringBackend,
+        text: &str,
+        corpus: &[String],
+    ) -> Result<LlmResult, String> {
+        let mut result = backend.score_text(text).await?;
+        self.apply_corpus_novelty(&mut result, text, corpus).await?;
+        Ok(result)
+    }
+
+    /// Overrides `result.score.novelty` with `1 - max_similarity` against
+    /// `corpus` (clamped to `0..1`), so novelty reflects actual semantic
+    /// overlap with things already said rather than a number the LLM
+    /// invents. The nearest neighbor and its similarity are recorded on
+    /// `result.trace` so a caller can see why something was judged
+    /// unoriginal. Leaves `result` untouched if `corpus` is empty.
+    pub async fn apply_corpus_novelty(
+        &self,
+        result: &mut LlmResult,
+        text: &str,
+        corpus: &[String],
+    ) -> Result<(), String> {
+        if corpus.is_empty() {
+            return Ok(());
+        }
+
+        let target = self.embed(text).await?;
+        let mut nearest: Option<(usize, f64)> = None;
+        for (index, candidate) in corpus.iter().enumerate() {
+            let embedding = self.embed(candidate).await?;
+            let similarity = cosine_similarity(&target, &embedding);
+            if nearest.map_or(true, |(_, best)| similarity > best) {
+                nearest = Some((index, similarity));
+            }
+        }
+
+        if let Some((index, similarity)) = nearest {
+            result.score.novelty = clamp01(1.0 - similarity);
+            result.trace.novelty_neighbor_text = Some(corpus[index].clone());
+            result.trace.novelty_neighbor_similarity = Some(similarity);
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct EmbeddingsRequest {
+    model: String,
+    input: String,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingsDatum>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsDatum {
+    embedding: Vec<f64>,
+}
+
+/// Cosine similarity between two embedding vectors, clamped to `-1..1`.
+/// Returns `0.0` for mismatched lengths or a zero vector rather than NaN.
+pub fn cosine_similarity(a: &[f64], b: &[f64]) -> f64 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f64 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f64>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    (dot / (norm_a * norm_b)).clamp(-1.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cosine_similarity;
+
+    #[test]
+    fn identical_vectors_are_maximally_similar() {
+        assert!((cosine_similarity(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0]) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn orthogonal_vectors_have_zero_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[0.0, 1.0])).abs() < 1e-9);
+    }
+
+    #[test]
+    fn mismatched_lengths_are_not_similar() {
+        assert_eq!(cosine_similarity(&[1.0, 2.0], &[1.0]), 0.0);
+    }
+}
@@ -0,0 +1,74 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Counts user-perceived characters (grapheme clusters) rather than Unicode
+/// scalar values, so combining diacritics and multi-codepoint emoji count
+/// once each instead of once per codepoint.
+pub fn grapheme_count(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// Counts emoji grapheme clusters in `text`. A cluster counts as one emoji
+/// if its leading scalar falls in a known Unicode emoji block or carries
+/// Emoji_Presentation, regardless of how many codepoints (ZWJ joiners,
+/// variation selectors, skin-tone/flag modifiers) follow it in the cluster.
+pub fn count_emoji_clusters(text: &str) -> usize {
+    text.graphemes(true)
+        .filter(|cluster| is_emoji_cluster(cluster))
+        .count()
+}
+
+fn is_emoji_cluster(cluster: &str) -> bool {
+    cluster
+        .chars()
+        .next()
+        .map(is_emoji_leading_scalar)
+        .unwrap_or(false)
+}
+
+/// Known Unicode emoji blocks (Emoticons, Misc Symbols & Pictographs,
+/// Transport & Map, Supplemental Symbols & Pictographs, Symbols &
+/// Pictographs Extended-A, Dingbats, Misc Symbols, and Regional Indicators
+/// for flag sequences). This approximates the Emoji_Presentation property
+/// without a full Unicode data table.
+fn is_emoji_leading_scalar(ch: char) -> bool {
+    let code = ch as u32;
+    matches!(code,
+        0x1F300..=0x1F5FF
+        | 0x1F600..=0x1F64F
+        | 0x1F680..=0x1F6FF
+        | 0x1F900..=0x1F9FF
+        | 0x1FA70..=0x1FAFF
+        | 0x1F1E6..=0x1F1FF
+        | 0x2600..=0x26FF
+        | 0x2700..=0x27BF
+        | 0x2300..=0x23FF
+        | 0x2B00..=0x2BFF
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_flag_sequence_as_one_emoji() {
+        let flag = "\u{1F1FA}\u{1F1F8}"; // regional indicators U+S -> US flag
+        assert_eq!(count_emoji_clusters(flag), 1);
+        assert_eq!(grapheme_count(flag), 1);
+    }
+
+    #[test]
+    fn counts_zwj_family_sequence_as_one_emoji() {
+        // man + ZWJ + woman + ZWJ + girl + ZWJ + boy
+        let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}";
+        assert_eq!(count_emoji_clusters(family), 1);
+        assert_eq!(grapheme_count(family), 1);
+    }
+
+    #[test]
+    fn does_not_inflate_count_for_combining_diacritics() {
+        let text = "cafe\u{0301}"; // "cafe" + combining acute accent -> "café"
+        assert_eq!(grapheme_count(text), 4);
+        assert_eq!(count_emoji_clusters(text), 0);
+    }
+}
@@ -0,0 +1,204 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Identifier for a post/tweet. Rejects empty or whitespace-only values so a
+/// blank string can't silently stand in for a missing id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PostId(String);
+
+/// Identifier for a post's author. Kept distinct from `PostId`/`UserId` so the
+/// compiler catches an id passed to the wrong slot.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AuthorId(String);
+
+/// Identifier for the viewing/ranking user.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UserId(String);
+
+macro_rules! impl_validated_id {
+    ($name:ident) => {
+        impl $name {
+            pub fn new(value: impl Into<String>) -> Result<Self, String> {
+                let value = value.into();
+                if value.trim().is_empty() {
+                    return Err(format!(
+                        "{} must not be empty or whitespace",
+                        stringify!($name)
+                    ));
+                }
+                Ok(Self(value))
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_string(self) -> String {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = String;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                $name::new(value)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = String;
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                $name::new(value)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.0)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                $name::new(value).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+impl_validated_id!(PostId);
+impl_validated_id!(AuthorId);
+impl_validated_id!(UserId);
+
+/// A validated instant, serialized/deserialized as an RFC3339 string while
+/// still exposing epoch seconds for arithmetic (e.g. `generate_synthetic_history`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    pub fn from_epoch_seconds(epoch_seconds: i64) -> Self {
+        Self(epoch_seconds)
+    }
+
+    pub fn epoch_seconds(&self) -> i64 {
+        self.0
+    }
+
+    pub fn now() -> Self {
+        let epoch_seconds = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or(0);
+        Self(epoch_seconds)
+    }
+
+    pub fn parse_rfc3339(value: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid RFC3339 timestamp: {}", value);
+        if value.len() < 19 {
+            return Err(invalid());
+        }
+        let year: i64 = value.get(0..4).ok_or_else(invalid)?.parse().map_err(|_| invalid())?;
+        let month: i64 = value.get(5..7).ok_or_else(invalid)?.parse().map_err(|_| invalid())?;
+        let day: i64 = value.get(8..10).ok_or_else(invalid)?.parse().map_err(|_| invalid())?;
+        let hour: i64 = value.get(11..13).ok_or_else(invalid)?.parse().map_err(|_| invalid())?;
+        let minute: i64 = value.get(14..16).ok_or_else(invalid)?.parse().map_err(|_| invalid())?;
+        let second: i64 = value.get(17..19).ok_or_else(invalid)?.parse().map_err(|_| invalid())?;
+
+        let days = days_from_civil(year, month, day);
+        let epoch_seconds = days * 86_400 + hour * 3600 + minute * 60 + second;
+
+        // Bytes 19+ are optional: fractional seconds (`.123`), then either
+        // `Z` or an explicit `+HH:MM`/`-HH:MM` offset. Fractional seconds
+        // don't affect whole-second epoch arithmetic and are dropped; the
+        // offset does and must be applied, or a feed emitting local-offset
+        // timestamps would be silently misread as UTC.
+        let mut rest = &value[19..];
+        if let Some(stripped) = rest.strip_prefix('.') {
+            let digits = stripped.find(|c: char| !c.is_ascii_digit()).unwrap_or(stripped.len());
+            rest = &stripped[digits..];
+        }
+        let offset_seconds = match rest {
+            "" | "Z" | "z" => 0,
+            _ => {
+                let sign = match rest.as_bytes()[0] {
+                    b'+' => 1,
+                    b'-' => -1,
+                    _ => return Err(invalid()),
+                };
+                let offset_hour: i64 = rest.get(1..3).ok_or_else(invalid)?.parse().map_err(|_| invalid())?;
+                let offset_minute: i64 = rest.get(4..6).ok_or_else(invalid)?.parse().map_err(|_| invalid())?;
+                sign * (offset_hour * 3600 + offset_minute * 60)
+            }
+        };
+
+        // A timestamp like `...+05:00` means the wall-clock fields above are
+        // 5 hours ahead of UTC, so subtract the offset to get the UTC instant.
+        Ok(Self(epoch_seconds - offset_seconds))
+    }
+
+    pub fn to_rfc3339(&self) -> String {
+        let days = self.0.div_euclid(86_400);
+        let remainder = self.0.rem_euclid(86_400);
+        let (year, month, day) = civil_from_days(days);
+        let hour = remainder / 3600;
+        let minute = (remainder % 3600) / 60;
+        let second = remainder % 60;
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year, month, day, hour, minute, second
+        )
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_rfc3339())
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_rfc3339())
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Timestamp::parse_rfc3339(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+// Howard Hinnant's civil-calendar algorithms: http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(z: i64) -> (i64, i64, i64) {
+    let z = z + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    (if m <= 2 { y + 1 } else { y }, m, d)
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A language-specific set of hook words and reply/share CTAs used by
+/// `extract_text_features`. Keyed by a lowercase language tag (`"en"`,
+/// `"ja"`, ...) on `ScoringConfig::lexicons`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanguageLexicon {
+    pub hook_words: Vec<String>,
+    pub cta_share: Vec<String>,
+    pub cta_reply: Vec<String>,
+}
+
+/// The built-in lexicons, seeded with the original English word lists plus a
+/// handful of other scripts `detect_language` can recognize. Operators can
+/// override or extend these via `ScoringConfig`.
+pub fn default_lexicons() -> HashMap<String, LanguageLexicon> {
+    let mut lexicons = HashMap::new();
+
+    lexicons.insert(
+        "en".to_string(),
+        LanguageLexicon {
+            hook_words: strings(&[
+                "how", "why", "what", "stop", "new", "breaking", "secret", "tips", "guide",
+                "learn", "thread", "facts", "proof", "mistakes", "warning",
+            ]),
+            cta_share: strings(&["retweet", "repost", "share", "rt ", "boost"]),
+            cta_reply: strings(&[
+                "thoughts",
+                "what do you think",
+                "agree",
+                "disagree",
+                "reply",
+                "comment",
+            ]),
+        },
+    );
+
+    lexicons.insert(
+        "ja".to_string(),
+        LanguageLexicon {
+            hook_words: strings(&["速報", "衝撃", "必見", "秘密", "注意", "警告"]),
+            cta_share: strings(&["リツイート", "拡散希望", "シェア"]),
+            cta_reply: strings(&["教えて", "コメント", "思う", "どう思う"]),
+        },
+    );
+
+    lexicons.insert(
+        "ar".to_string(),
+        LanguageLexicon {
+            hook_words: strings(&["عاجل", "سر", "تحذير", "كيف", "لماذا"]),
+            cta_share: strings(&["شارك", "إعادة النشر"]),
+            cta_reply: strings(&["رأيك", "علق", "هل توافق"]),
+        },
+    );
+
+    lexicons
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+fn script_histogram(text: &str) -> HashMap<&'static str, usize> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+
+    for ch in text.chars() {
+        let code = ch as u32;
+        let script = if (0x3040..=0x30ff).contains(&code) || (0x4e00..=0x9fff).contains(&code) {
+            Some("ja")
+        } else if (0xac00..=0xd7a3).contains(&code) {
+            Some("ko")
+        } else if (0x0600..=0x06ff).contains(&code) {
+            Some("ar")
+        } else if (0x0400..=0x04ff).contains(&code) {
+            Some("ru")
+        } else if (0x0900..=0x097f).contains(&code) {
+            Some("hi")
+        } else if (0x0370..=0x03ff).contains(&code) {
+            Some("el")
+        } else if ch.is_alphabetic() {
+            Some("en")
+        } else {
+            None
+        };
+
+        if let Some(script) = script {
+            *counts.entry(script).or_insert(0) += 1;
+        }
+    }
+
+    counts
+}
+
+/// Detects the dominant script of `text` via a simple per-character
+/// frequency heuristic and maps it to a language tag. Returns `None` for
+/// text with no recognizable alphabetic content (e.g. pure emoji/numbers).
+pub fn detect_language(text: &str) -> Option<String> {
+    script_histogram(text)
+        .into_iter()
+        .max_by_key(|&(_, count)| count)
+        .map(|(lang, _)| lang.to_string())
+}
+
+/// True when `text` contains a meaningful amount of more than one script,
+/// e.g. an English post with an untranslated Japanese hashtag. Used to warn
+/// that hook/CTA detection only runs against the dominant language.
+pub fn has_mixed_scripts(text: &str) -> bool {
+    script_histogram(text)
+        .values()
+        .filter(|&&count| count >= 3)
+        .count()
+        > 1
+}
@@ -1,21 +1,41 @@
+pub mod cascade;
 pub mod config;
+pub mod config_watcher;
 pub mod calibration;
+pub mod graphemes;
+pub mod ids;
+pub mod lexicon;
+pub mod monte_carlo;
 pub mod phoenix_client;
+pub mod query;
+pub mod saturation;
+pub mod schedule;
 pub mod scoring;
 pub mod user;
 
+use arc_swap::ArcSwap;
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::sync::Arc;
 
-use crate::config::ScoringConfig;
-use crate::scoring::{AuthorDiversityScorer, OonScorer, ScoredCandidate, ScoringPipeline, WeightedScorer};
+use crate::cascade::{simulate_cascade, CascadeConfig};
+use crate::config::{ScoringConfig, TierThresholds};
+use crate::graphemes::{count_emoji_clusters, grapheme_count};
+use crate::lexicon::detect_language;
+use crate::scoring::{
+    AuthorDiversityScorer, ModerationScorer, OonScorer, ScoredCandidate, ScoringPipeline,
+    WeightedScorer,
+};
 
 #[derive(Debug, Clone, Copy)]
 pub enum MediaType {
     None,
     Image,
-    Video,
     Gif,
+    Video { is_live: bool },
+    Carousel { count: u8 },
 }
 
 impl MediaType {
@@ -23,8 +43,10 @@ impl MediaType {
         match value.to_lowercase().as_str() {
             "none" | "text" => Some(MediaType::None),
             "image" | "photo" | "pic" => Some(MediaType::Image),
-            "video" | "vid" => Some(MediaType::Video),
             "gif" => Some(MediaType::Gif),
+            "video" | "vid" => Some(MediaType::Video { is_live: false }),
+            "live" | "livestream" | "broadcast" => Some(MediaType::Video { is_live: true }),
+            "carousel" | "gallery" | "album" => Some(MediaType::Carousel { count: 2 }),
             _ => None,
         }
     }
@@ -34,12 +56,16 @@ impl MediaType {
             MediaType::None => 0.0,
             MediaType::Image => 0.4,
             MediaType::Gif => 0.6,
-            MediaType::Video => 0.8,
+            MediaType::Video { is_live: false } => 0.8,
+            MediaType::Video { is_live: true } => 0.9,
+            MediaType::Carousel { count } => {
+                (0.4 + 0.08 * count.saturating_sub(1) as f64).min(0.85)
+            }
         }
     }
 
     pub fn is_video(self) -> f64 {
-        if matches!(self, MediaType::Video) {
+        if matches!(self, MediaType::Video { .. }) {
             1.0
         } else {
             0.0
@@ -47,12 +73,24 @@ impl MediaType {
     }
 
     pub fn is_image(self) -> f64 {
-        if matches!(self, MediaType::Image | MediaType::Gif) {
+        if matches!(self, MediaType::Image | MediaType::Gif | MediaType::Carousel { .. }) {
             1.0
         } else {
             0.0
         }
     }
+
+    pub fn is_live(self) -> bool {
+        matches!(self, MediaType::Video { is_live: true })
+    }
+
+    /// Number of items in a gallery/carousel; 1 for any non-carousel media.
+    pub fn media_count(self) -> u8 {
+        match self {
+            MediaType::Carousel { count } => count.max(1),
+            _ => 1,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -106,6 +144,18 @@ impl Default for SimulatorInput {
     }
 }
 
+impl SimulatorInput {
+    /// Number of items in a gallery/carousel, read through from `media`.
+    pub fn media_count(&self) -> u8 {
+        self.media.media_count()
+    }
+
+    /// Whether `media` is a live video/broadcast, read through from `media`.
+    pub fn is_live(&self) -> bool {
+        self.media.is_live()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TextFeatures {
     pub char_count: usize,
@@ -122,6 +172,10 @@ pub struct TextFeatures {
     pub has_hook_word: bool,
     pub cta_share: bool,
     pub cta_reply: bool,
+    /// Dominant language detected in the post text, or `None` if it has no
+    /// recognizable alphabetic content. `build_suggestions` uses this to
+    /// warn when no lexicon is configured for the detected language.
+    pub lang: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -145,6 +199,17 @@ pub struct LlmTrace {
     pub prompt_tokens: Option<u32>,
     pub completion_tokens: Option<u32>,
     pub total_tokens: Option<u32>,
+    /// How `raw_response` was turned into an `LlmScore`: `"json_object"` for
+    /// a provider-guaranteed JSON body, `"tool_call"` for a forced
+    /// `report_virality` tool/function call, or `"text"` for the
+    /// brace-matching fallback used against providers with no structured
+    /// output support.
+    pub output_mode: String,
+    /// The most similar tweet found in the corpus passed to
+    /// `EmbeddingsClient::score_text_with_corpus`, and its cosine similarity
+    /// to the candidate text. `None` unless corpus-based novelty scoring ran.
+    pub novelty_neighbor_text: Option<String>,
+    pub novelty_neighbor_similarity: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -163,7 +228,7 @@ pub struct Signals {
     pub time_score: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ActionProbs {
     pub like: f64,
     pub reply: f64,
@@ -183,6 +248,8 @@ pub struct ActionProbs {
     pub block: f64,
     pub mute: f64,
     pub report: f64,
+    #[serde(default)]
+    pub hide_post: f64,
     pub dwell_time: f64,
 }
 
@@ -191,6 +258,7 @@ pub enum ScoringMode {
     Heuristic,
     Phoenix,
     Hybrid { phoenix_weight: f64 },
+    Cascade { seed: u64 },
 }
 
 impl ScoringMode {
@@ -199,6 +267,7 @@ impl ScoringMode {
             ScoringMode::Heuristic => "heuristic",
             ScoringMode::Phoenix => "phoenix",
             ScoringMode::Hybrid { .. } => "hybrid",
+            ScoringMode::Cascade { .. } => "cascade",
         }
     }
 
@@ -206,12 +275,12 @@ impl ScoringMode {
         match self {
             ScoringMode::Hybrid { phoenix_weight } => phoenix_weight,
             ScoringMode::Phoenix => 1.0,
-            ScoringMode::Heuristic => 0.0,
+            ScoringMode::Heuristic | ScoringMode::Cascade { .. } => 0.0,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ViralityTier {
     Low,
     Moderate,
@@ -254,14 +323,15 @@ pub struct SimulationOutput {
     pub suggestions: Vec<String>,
     pub llm: Option<LlmScore>,
     pub llm_trace: Option<LlmTrace>,
+    pub cascade_depth: usize,
+    pub reproduction_number: f64,
 }
 
-pub fn extract_text_features(text: &str) -> TextFeatures {
+pub fn extract_text_features(text: &str, config: &ScoringConfig) -> TextFeatures {
     let mut hashtags = 0usize;
     let mut mentions = 0usize;
     let mut questions = 0usize;
     let mut exclamations = 0usize;
-    let mut emoji_count = 0usize;
     let mut uppercase = 0usize;
     let mut letters = 0usize;
     let mut urls = 0usize;
@@ -272,16 +342,12 @@ pub fn extract_text_features(text: &str) -> TextFeatures {
             '@' => mentions += 1,
             '?' => questions += 1,
             '!' => exclamations += 1,
-            _ => {
-                if ch as u32 > 0x7f {
-                    emoji_count += 1;
-                }
-            }
+            _ => {}
         }
 
-        if ch.is_ascii_alphabetic() {
+        if ch.is_alphabetic() {
             letters += 1;
-            if ch.is_ascii_uppercase() {
+            if ch.is_uppercase() {
                 uppercase += 1;
             }
         }
@@ -296,7 +362,7 @@ pub fn extract_text_features(text: &str) -> TextFeatures {
     let mut word_total = 0usize;
     let mut word_count = 0usize;
     for word in text.split_whitespace() {
-        let len = word.chars().filter(|c| c.is_ascii_alphabetic()).count();
+        let len = word.chars().filter(|c| c.is_alphabetic()).count();
         if len > 0 {
             word_total += len;
             word_count += 1;
@@ -318,67 +384,167 @@ pub fn extract_text_features(text: &str) -> TextFeatures {
     let starts_with_number = text
         .chars()
         .find(|c| !c.is_whitespace())
-        .map(|c| c.is_ascii_digit())
+        .map(|c| c.is_numeric())
         .unwrap_or(false);
 
-    let hook_words = [
-        "how", "why", "what", "stop", "new", "breaking", "secret", "tips", "guide", "learn",
-        "thread", "facts", "proof", "mistakes", "warning",
-    ];
-    let has_hook_word = hook_words.iter().any(|word| lowercase.contains(word));
+    let lang = detect_language(text);
+    let lexicon = lang.as_deref().and_then(|lang| config.lexicons.get(lang));
 
-    let cta_share = ["retweet", "repost", "share", "rt ", "boost"].iter().any(|w| {
-        lowercase.contains(w)
-    });
-    let cta_reply = ["thoughts", "what do you think", "agree", "disagree", "reply", "comment"]
-        .iter()
-        .any(|w| lowercase.contains(w));
+    let has_hook_word = lexicon
+        .map(|lexicon| {
+            lexicon
+                .hook_words
+                .iter()
+                .any(|word| lowercase.contains(word.as_str()))
+        })
+        .unwrap_or(false);
+    let cta_share = lexicon
+        .map(|lexicon| {
+            lexicon
+                .cta_share
+                .iter()
+                .any(|word| lowercase.contains(word.as_str()))
+        })
+        .unwrap_or(false);
+    let cta_reply = lexicon
+        .map(|lexicon| {
+            lexicon
+                .cta_reply
+                .iter()
+                .any(|word| lowercase.contains(word.as_str()))
+        })
+        .unwrap_or(false);
 
     TextFeatures {
-        char_count: text.chars().count(),
+        char_count: grapheme_count(text),
         word_count,
         hashtags,
         mentions,
         urls,
         questions,
         exclamations,
-        emoji_count,
+        emoji_count: count_emoji_clusters(text),
         uppercase_ratio,
         avg_word_len,
         starts_with_number,
         has_hook_word,
         cta_share,
         cta_reply,
+        lang,
     }
 }
 
 fn load_scoring_config() -> ScoringConfig {
     ScoringConfig::load(None)
-        .map(|(config, _)| config)
+        .map(|(config, _, _)| config)
         .unwrap_or_default()
 }
 
+/// Reusable scorer that loads `ScoringConfig` once and keeps it behind an
+/// `ArcSwap` so `reload_config` can hot-swap it without readers blocking.
+/// `simulate`/`simulate_with_llm`/`simulate_batch` are thin wrappers over a
+/// lazily-initialized global instance so callers don't re-parse the config
+/// file on every post.
+pub struct Simulator {
+    config: ArcSwap<ScoringConfig>,
+}
+
+impl Simulator {
+    pub fn new(config: ScoringConfig) -> Self {
+        Self {
+            config: ArcSwap::from_pointee(config),
+        }
+    }
+
+    pub fn config(&self) -> Arc<ScoringConfig> {
+        self.config.load_full()
+    }
+
+    pub fn reload_config(&self, config: ScoringConfig) {
+        self.config.store(Arc::new(config));
+    }
+
+    pub fn simulate_one(&self, input: &SimulatorInput) -> SimulationOutput {
+        let config = self.config();
+        simulate_with_mode(input, None, None, ScoringMode::Heuristic, None, &config)
+    }
+
+    pub fn simulate_one_with_llm(
+        &self,
+        input: &SimulatorInput,
+        llm: Option<&LlmScore>,
+        llm_trace: Option<&LlmTrace>,
+    ) -> SimulationOutput {
+        let config = self.config();
+        simulate_with_mode(input, llm, llm_trace, ScoringMode::Heuristic, None, &config)
+    }
+
+    /// Scores `inputs` in parallel via rayon, reusing the cached config and
+    /// pipeline construction across the whole batch.
+    pub fn simulate_many(&self, inputs: &[SimulatorInput]) -> Vec<SimulationOutput> {
+        let config = self.config();
+        inputs
+            .par_iter()
+            .map(|input| simulate_with_mode(input, None, None, ScoringMode::Heuristic, None, &config))
+            .collect()
+    }
+
+    /// Scores `inputs` in parallel, then filters/sorts per `query` (see
+    /// `query::Query`), or in input order by descending `final_score` when
+    /// `query` is `None`.
+    pub fn simulate_batch(
+        &self,
+        inputs: &[SimulatorInput],
+        query: Option<&str>,
+    ) -> Result<Vec<SimulationOutput>, String> {
+        let config = self.config();
+        let candidates: Vec<(SimulatorInput, SimulationOutput)> = inputs
+            .par_iter()
+            .map(|input| {
+                let output =
+                    simulate_with_mode(input, None, None, ScoringMode::Heuristic, None, &config);
+                (input.clone(), output)
+            })
+            .collect();
+
+        let query = match query {
+            Some(source) => query::Query::parse(source)?,
+            None => query::Query::default(),
+        };
+
+        Ok(query.apply(&candidates, &config))
+    }
+}
+
+static GLOBAL_SIMULATOR: Lazy<Simulator> = Lazy::new(|| Simulator::new(load_scoring_config()));
+
 pub fn simulate(input: &SimulatorInput) -> SimulationOutput {
-    let config = load_scoring_config();
-    simulate_with_mode(input, None, None, ScoringMode::Heuristic, None, &config)
+    GLOBAL_SIMULATOR.simulate_one(input)
+}
+
+/// Scores a batch of draft `SimulatorInput`s and returns them filtered and
+/// ranked per `query` (see `query::Query` for the DSL grammar), or in
+/// input order by descending `final_score` when `query` is `None`.
+pub fn simulate_batch(
+    inputs: &[SimulatorInput],
+    query: Option<&str>,
+) -> Result<Vec<SimulationOutput>, String> {
+    GLOBAL_SIMULATOR.simulate_batch(inputs, query)
 }
 
+#[tracing::instrument(skip_all, fields(followers = input.followers, media = ?input.media))]
 pub fn simulate_with_llm(
     input: &SimulatorInput,
     llm: Option<&LlmScore>,
     llm_trace: Option<&LlmTrace>,
 ) -> SimulationOutput {
-    let config = load_scoring_config();
-    simulate_with_mode(
-        input,
-        llm,
-        llm_trace,
-        ScoringMode::Heuristic,
-        None,
-        &config,
-    )
+    GLOBAL_SIMULATOR.simulate_one_with_llm(input, llm, llm_trace)
 }
 
+#[tracing::instrument(
+    skip(input, llm, llm_trace, phoenix_actions, scoring_config),
+    fields(followers = input.followers, media = ?input.media, mode = scoring_mode.label(), score = tracing::field::Empty)
+)]
 pub fn simulate_with_mode(
     input: &SimulatorInput,
     llm: Option<&LlmScore>,
@@ -387,7 +553,7 @@ pub fn simulate_with_mode(
     phoenix_actions: Option<&ActionProbs>,
     scoring_config: &ScoringConfig,
 ) -> SimulationOutput {
-    let features = extract_text_features(&input.text);
+    let features = extract_text_features(&input.text, scoring_config);
     let media_score = input.media.media_score();
     let has_link = input
         .has_link_override
@@ -423,14 +589,15 @@ pub fn simulate_with_mode(
     let mut controversy = clamp01(input.controversy);
     let mut sentiment = input.sentiment.max(-1.0).min(1.0);
 
+    let blend = &scoring_config.llm_blend;
     let mut hook = base_hook;
     let mut clarity = base_clarity;
     if let Some(score) = llm {
-        hook = blend_signal(hook, clamp01(score.hook), 0.6);
-        clarity = blend_signal(clarity, clamp01(score.clarity), 0.6);
-        novelty = blend_signal(novelty, clamp01(score.novelty), 0.6);
-        controversy = blend_signal(controversy, clamp01(score.controversy), 0.5);
-        sentiment = blend_sentiment(sentiment, score.sentiment, 0.5);
+        hook = blend_signal(hook, clamp01(score.hook), blend.hook_weight);
+        clarity = blend_signal(clarity, clamp01(score.clarity), blend.clarity_weight);
+        novelty = blend_signal(novelty, clamp01(score.novelty), blend.novelty_weight);
+        controversy = blend_signal(controversy, clamp01(score.controversy), blend.controversy_weight);
+        sentiment = blend_sentiment(sentiment, score.sentiment, blend.sentiment_weight);
     }
 
     let mut shareability = clamp01(
@@ -440,7 +607,7 @@ pub fn simulate_with_mode(
             + 0.1 * bool_to_f64(features.cta_share),
     );
     if let Some(score) = llm {
-        shareability = blend_signal(shareability, clamp01(score.shareability), 0.6);
+        shareability = blend_signal(shareability, clamp01(score.shareability), blend.shareability_weight);
     }
 
     let content_quality = clamp01(0.45 * clarity + 0.25 * hook + 0.2 * novelty + 0.1 * timeliness);
@@ -478,6 +645,8 @@ pub fn simulate_with_mode(
     let audience_alignment =
         clamp01(0.6 * audience_fit + 0.2 * (1.0 - topic_saturation) + 0.2 * ratio_score);
 
+    let moderation = ModerationScorer::new(scoring_config.moderation.clone()).analyze(&input.text);
+
     let negative_sentiment = (-sentiment).max(0.0);
     let caps_risk = clamp01(features.uppercase_ratio / 0.35) * 0.2;
     let negative_risk = clamp01(
@@ -485,7 +654,8 @@ pub fn simulate_with_mode(
             + 0.25 * spamminess
             + 0.15 * negative_sentiment
             + caps_risk
-            + 0.1 * topic_saturation,
+            + 0.1 * topic_saturation
+            + 0.5 * moderation.toxicity,
     );
 
     let positive_signal = clamp01(0.4 * content_quality + 0.35 * author_quality + 0.25 * audience_alignment);
@@ -498,6 +668,8 @@ pub fn simulate_with_mode(
     let cta_share = bool_to_f64(features.cta_share);
     let is_video = input.media.is_video();
     let is_image = input.media.is_image();
+    let is_live = bool_to_f64(input.media.is_live());
+    let carousel_lift = ((input.media.media_count() as f64 - 1.0) * 0.08).min(0.4);
 
     let like = sigmoid(base + 0.6 * media_score + 0.2 * sentiment.max(0.0));
     let reply = sigmoid(
@@ -508,21 +680,35 @@ pub fn simulate_with_mode(
     let quote = sigmoid(base + 0.4 * controversy + 0.2 * novelty);
     let click = sigmoid(base + 0.9 * link_flag + 0.2 * hook);
     let profile_click = sigmoid(base + 0.5 * author_quality + 0.2 * novelty);
-    let video_view = sigmoid(base + 1.2 * is_video + 0.2 * hook);
-    let photo_expand = sigmoid(base + 1.0 * is_image + 0.1 * hook);
+    let video_view = sigmoid(base + 1.2 * is_video + 0.3 * is_live + 0.2 * hook);
+    let photo_expand = sigmoid(base + 1.0 * is_image + 0.5 * carousel_lift + 0.1 * hook);
     let share = sigmoid(base + 0.5 * shareability + 0.2 * novelty);
     let share_dm = sigmoid(base + 0.35 * shareability + 0.1 * novelty - 0.1 * link_flag);
     let share_link = sigmoid(base + 0.25 * shareability + 0.2 * link_flag);
-    let dwell = sigmoid(base + 0.2 * length_score + 0.4 * media_score - 0.2 * link_flag);
+    let dwell = sigmoid(
+        base + 0.2 * length_score + 0.4 * media_score + 0.3 * carousel_lift + 0.4 * is_live
+            - 0.2 * link_flag,
+    );
     let follow_author = sigmoid(base + 0.6 * author_quality + 0.2 * hook);
     let quoted_click = sigmoid(base + 0.4 * controversy + 0.2 * hook + 0.1 * novelty);
     let not_interested = sigmoid(
-        -1.0 + 2.2 * negative_risk + 0.6 * topic_saturation - 0.8 * audience_alignment,
+        -1.0 + 2.2 * negative_risk + 0.6 * topic_saturation - 0.8 * audience_alignment
+            + 0.3 * moderation.toxicity,
+    );
+    let block = sigmoid(-2.0 + 2.6 * negative_risk + 0.6 * controversy + 0.4 * moderation.toxicity);
+    let mute = sigmoid(-1.8 + 2.3 * negative_risk + 0.8 * topic_saturation + 0.4 * moderation.toxicity);
+    let report = sigmoid(-2.4 + 2.8 * negative_risk + 0.6 * controversy + 0.5 * moderation.toxicity);
+    let hide_post = sigmoid(
+        -1.6 + 2.0 * negative_risk + 0.5 * topic_saturation - 0.5 * audience_alignment
+            + 0.3 * moderation.toxicity,
+    );
+    let dwell_time = estimate_dwell_time(
+        features.char_count,
+        media_score,
+        dwell,
+        carousel_lift,
+        input.media.is_live(),
     );
-    let block = sigmoid(-2.0 + 2.6 * negative_risk + 0.6 * controversy);
-    let mute = sigmoid(-1.8 + 2.3 * negative_risk + 0.8 * topic_saturation);
-    let report = sigmoid(-2.4 + 2.8 * negative_risk + 0.6 * controversy);
-    let dwell_time = estimate_dwell_time(features.char_count, media_score, dwell);
 
     let heuristic_actions = ActionProbs {
         like,
@@ -543,11 +729,12 @@ pub fn simulate_with_mode(
         block,
         mute,
         report,
+        hide_post,
         dwell_time,
     };
 
     let actions = match scoring_mode {
-        ScoringMode::Heuristic => heuristic_actions.clone(),
+        ScoringMode::Heuristic | ScoringMode::Cascade { .. } => heuristic_actions.clone(),
         ScoringMode::Phoenix => phoenix_actions
             .cloned()
             .unwrap_or_else(|| heuristic_actions.clone()),
@@ -585,34 +772,61 @@ pub fn simulate_with_mode(
     let final_score = candidate.score;
 
     let time_score = time_of_day_score(input.hour_of_day);
-    let active_fraction = 0.015 + 0.08 * time_score;
-    let impressions_in = (input.followers as f64)
-        * active_fraction
-        * (0.6 + 0.4 * audience_alignment)
-        .max(0.0);
 
-    let oon_seed = 300.0 + 1400.0 * positive_signal;
-    let oon_reach_multiplier = 1.0 + clamp01((weighted_score - 1.0) / 3.0) * 4.0;
-    let mut impressions_oon = oon_seed
-        * oon_reach_multiplier
-        * (0.5 + 0.5 * content_quality)
-        * (1.0 - 0.7 * topic_saturation)
-        * (1.0 - 0.5 * negative_risk);
+    let mut cascade_depth = 0usize;
+    let mut reproduction_number = 0.0;
+
+    let (impressions_in, impressions_oon, expected_unique_engagements) =
+        if let ScoringMode::Cascade { seed } = scoring_mode {
+            let result = simulate_cascade(input.followers, &actions, &CascadeConfig::default(), seed);
+            cascade_depth = result.cascade_depth;
+            reproduction_number = result.reproduction_number;
+            (
+                result.impressions_in,
+                result.impressions_oon,
+                result.expected_unique_engagements,
+            )
+        } else {
+            let active_fraction = 0.015 + 0.08 * time_score;
+            let impressions_in = (input.followers as f64)
+                * active_fraction
+                * (0.6 + 0.4 * audience_alignment)
+                .max(0.0);
+
+            let oon_seed = 300.0 + 1400.0 * positive_signal;
+            let oon_reach_multiplier = 1.0 + clamp01((weighted_score - 1.0) / 3.0) * 4.0;
+            let mut impressions_oon = oon_seed
+                * oon_reach_multiplier
+                * (0.5 + 0.5 * content_quality)
+                * (1.0 - 0.7 * topic_saturation)
+                * (1.0 - 0.5 * negative_risk);
+
+            if impressions_oon.is_nan() || impressions_oon.is_sign_negative() {
+                impressions_oon = 0.0;
+            }
 
-    if impressions_oon.is_nan() || impressions_oon.is_sign_negative() {
-        impressions_oon = 0.0;
-    }
+            let impressions_total = impressions_in + impressions_oon;
+            let expected_unique_engagements = impressions_total * unique_engagement_rate(&actions);
+            (impressions_in, impressions_oon, expected_unique_engagements)
+        };
 
     let impressions_total = impressions_in + impressions_oon;
-
     let action_volume_rate = action_volume_rate(&actions);
-    let unique_engagement_rate = unique_engagement_rate(&actions);
+    let unique_engagement_rate = if impressions_total > 0.0 {
+        clamp01(expected_unique_engagements / impressions_total)
+    } else {
+        0.0
+    };
     let expected_action_volume = impressions_total * action_volume_rate;
-    let expected_unique_engagements = impressions_total * unique_engagement_rate;
 
     let raw = (final_score - 1.0) * 0.8 + (log10_safe(impressions_total + 1.0) - 3.0) * 0.4;
     let score = 100.0 * sigmoid(raw);
-    let tier = tier_from_score(score);
+    let tier = tier_from_score(score, &scoring_config.tier_thresholds);
+
+    // Blend the predicted negative-action rate back into negative_risk so the
+    // reported signal reflects actual expected pushback, not just the text
+    // heuristics that seeded `not_interested`/`report`/`mute`/`block`/`hide_post`.
+    let negative_risk = clamp01(0.7 * negative_risk + 0.3 * negative_engagement_rate(&actions));
 
     let signals = Signals {
         length_score,
@@ -629,11 +843,23 @@ pub fn simulate_with_mode(
         time_score,
     };
 
-    let mut suggestions = build_suggestions(input, &features, &signals, &actions, weighted_score);
+    let mut suggestions =
+        build_suggestions(
+            input,
+            &features,
+            &signals,
+            &actions,
+            weighted_score,
+            &moderation,
+            scoring_config,
+        );
     if let Some(score) = llm {
         merge_suggestions(&mut suggestions, &score.suggestions);
     }
 
+    tracing::Span::current().record("score", score);
+    tracing::debug!(score, tier = tier.label(), "simulation complete");
+
     SimulationOutput {
         score,
         tier,
@@ -655,6 +881,8 @@ pub fn simulate_with_mode(
         suggestions,
         llm: llm.cloned(),
         llm_trace: llm_trace.cloned(),
+        cascade_depth,
+        reproduction_number,
     }
 }
 
@@ -691,18 +919,28 @@ fn derive_video_duration(input: &SimulatorInput) -> Option<f64> {
     if let Some(duration) = input.video_duration_seconds {
         return Some(duration.max(0.0));
     }
-    if matches!(input.media, MediaType::Video) {
-        return Some(15.0);
+    match input.media {
+        // Live broadcasts have no fixed duration to report.
+        MediaType::Video { is_live: true } => None,
+        MediaType::Video { is_live: false } => Some(15.0),
+        _ => None,
     }
-    None
 }
 
-fn estimate_dwell_time(char_count: usize, media_score: f64, dwell_prob: f64) -> f64 {
+fn estimate_dwell_time(
+    char_count: usize,
+    media_score: f64,
+    dwell_prob: f64,
+    carousel_lift: f64,
+    is_live: bool,
+) -> f64 {
     let base = 1.5 + (char_count as f64 / 80.0);
     let media_lift = 6.0 * media_score;
     let dwell_lift = 10.0 * dwell_prob;
-    let estimate = base + media_lift + dwell_lift;
-    estimate.max(0.0).min(60.0)
+    let carousel_bonus = 8.0 * carousel_lift;
+    let live_bonus = if is_live { 20.0 } else { 0.0 };
+    let estimate = base + media_lift + dwell_lift + carousel_bonus + live_bonus;
+    estimate.max(0.0).min(90.0)
 }
 
 fn blend_actions(base: &ActionProbs, overlay: &ActionProbs, weight: f64) -> ActionProbs {
@@ -728,6 +966,7 @@ fn blend_actions(base: &ActionProbs, overlay: &ActionProbs, weight: f64) -> Acti
         block: blend_prob(base.block, overlay.block),
         mute: blend_prob(base.mute, overlay.mute),
         report: blend_prob(base.report, overlay.report),
+        hide_post: blend_prob(base.hide_post, overlay.hide_post),
         dwell_time: blend_value(base.dwell_time, overlay.dwell_time),
     }
 }
@@ -749,8 +988,36 @@ fn build_suggestions(
     signals: &Signals,
     actions: &ActionProbs,
     weighted_score: f64,
+    moderation: &scoring::ModerationResult,
+    config: &ScoringConfig,
 ) -> Vec<String> {
     let mut suggestions = Vec::new();
+    match &features.lang {
+        Some(lang) if !config.lexicons.contains_key(lang) => {
+            suggestions.push(format!(
+                "No hook/CTA lexicon configured for detected language '{}'; add one to ScoringConfig.lexicons.",
+                lang
+            ));
+        }
+        None => {
+            suggestions.push(
+                "Could not detect a dominant language; hook/CTA detection was skipped."
+                    .to_string(),
+            );
+        }
+        _ => {}
+    }
+    if crate::lexicon::has_mixed_scripts(&input.text) {
+        suggestions.push(
+            "Post mixes multiple scripts; hook/CTA detection only runs against the dominant language.".to_string(),
+        );
+    }
+    if moderation.toxicity > 0.3 {
+        suggestions.push(format!(
+            "Content likely to trigger suppression ({}); revise before posting.",
+            moderation.categories.join(", ")
+        ));
+    }
     if features.char_count < 50 {
         suggestions.push("Add a clearer hook and more context; aim for ~80-200 characters.".to_string());
     }
@@ -784,6 +1051,9 @@ fn build_suggestions(
     if signals.negative_risk > 0.55 {
         suggestions.push("Tone down contentious framing to reduce not-interested/report signals.".to_string());
     }
+    if let Some(message) = dominant_negative_action_suggestion(actions) {
+        suggestions.push(message);
+    }
     if input.topic_saturation > 0.6 {
         suggestions.push("High topic saturation; use a unique angle or niche framing.".to_string());
     }
@@ -803,6 +1073,30 @@ fn build_suggestions(
     suggestions
 }
 
+/// When one negative action clearly dominates the rest, names it explicitly
+/// instead of the generic "tone down contentious framing" advice.
+fn dominant_negative_action_suggestion(actions: &ActionProbs) -> Option<String> {
+    let candidates = [
+        (actions.report, "Reports likely driven by contentious framing; soften claims or add sourcing."),
+        (actions.block, "Block rate looks elevated; avoid language that reads as targeting or hostile."),
+        (actions.mute, "Mute rate looks elevated; this may read as repetitive or off-topic to followers."),
+        (actions.hide_post, "Hide-post rate looks elevated; the framing may be landing as low-value or spammy."),
+        (actions.not_interested, "Not-interested rate looks elevated; align the topic more closely with audience interests."),
+    ];
+
+    let (dominant_value, dominant_message) = candidates
+        .iter()
+        .copied()
+        .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))?;
+
+    let total: f64 = candidates.iter().map(|(value, _)| value).sum();
+    if dominant_value > 0.08 && total > 0.0 && dominant_value / total > 0.5 {
+        Some(dominant_message.to_string())
+    } else {
+        None
+    }
+}
+
 fn merge_suggestions(base: &mut Vec<String>, extras: &[String]) {
     let mut seen: HashSet<String> = base.iter().map(|s| normalize_text(s)).collect();
     for suggestion in extras {
@@ -848,14 +1142,35 @@ fn positive_action_probs(actions: &ActionProbs) -> Vec<f64> {
     ]
 }
 
-fn tier_from_score(score: f64) -> ViralityTier {
-    if score < 35.0 {
+fn negative_action_probs(actions: &ActionProbs) -> Vec<f64> {
+    vec![
+        actions.not_interested,
+        actions.report,
+        actions.mute,
+        actions.block,
+        actions.hide_post,
+    ]
+}
+
+/// Probability that at least one negative action (not-interested, report,
+/// mute, block, hide) fires, mirroring `unique_engagement_rate`'s
+/// product-of-complements over the positive actions.
+fn negative_engagement_rate(actions: &ActionProbs) -> f64 {
+    let mut none_probability = 1.0;
+    for probability in negative_action_probs(actions) {
+        none_probability *= 1.0 - clamp01(probability);
+    }
+    clamp01(1.0 - none_probability)
+}
+
+fn tier_from_score(score: f64, thresholds: &TierThresholds) -> ViralityTier {
+    if score < thresholds.moderate {
         ViralityTier::Low
-    } else if score < 55.0 {
+    } else if score < thresholds.high {
         ViralityTier::Moderate
-    } else if score < 75.0 {
+    } else if score < thresholds.very_high {
         ViralityTier::High
-    } else if score < 90.0 {
+    } else if score < thresholds.breakout {
         ViralityTier::VeryHigh
     } else {
         ViralityTier::Breakout
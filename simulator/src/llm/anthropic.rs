@@ -0,0 +1,337 @@
+use async_trait::async_trait;
+use reqwest::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::env;
+use std::time::Instant;
+use tokio_stream::StreamExt;
+
+use super::{
+    build_trace, drain_sse_events, parse_score_value, prompt_text, report_virality_schema,
+    system_prompt, user_message, LlmResult, ScoringBackend, Usage, OUTPUT_MODE_TOOL_CALL,
+    REPORT_VIRALITY_TOOL,
+};
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// A Claude `/v1/messages`-shaped backend. Unlike the OpenAI-compatible
+/// shape, the system prompt is a top-level `system` field rather than a
+/// message with `role: "system"`, auth goes through `x-api-key` instead of
+/// a bearer token, and every request needs an `anthropic-version` header.
+/// Claude is forced to call the `report_virality` tool rather than asked to
+/// emit JSON prose, so `score_text`/`score_text_stream` parse its
+/// `tool_calls` arguments directly instead of brace-matching free text.
+#[derive(Clone)]
+pub struct AnthropicBackend {
+    client: reqwest::Client,
+    api_key: String,
+    api_base: String,
+    model: String,
+    max_tokens: u32,
+}
+
+impl AnthropicBackend {
+    pub fn from_env(model_override: Option<String>) -> Option<Self> {
+        let api_key = env::var("ANTHROPIC_API_KEY").ok()?;
+        let api_base = env::var("ANTHROPIC_API_BASE")
+            .unwrap_or_else(|_| "https://api.anthropic.com/v1".to_string());
+        let model = model_override
+            .or_else(|| env::var("ANTHROPIC_MODEL").ok())
+            .unwrap_or_else(|| "claude-3-5-sonnet-latest".to_string());
+        Some(Self::new(api_key, api_base, model))
+    }
+
+    pub fn new(api_key: String, api_base: String, model: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key,
+            api_base,
+            model,
+            max_tokens: 1024,
+        }
+    }
+
+    fn request(&self, url: &str) -> reqwest::RequestBuilder {
+        self.client
+            .post(url)
+            .header("x-api-key", self.api_key.as_str())
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .header(CONTENT_TYPE, "application/json")
+    }
+}
+
+#[async_trait]
+impl ScoringBackend for AnthropicBackend {
+    async fn score_text(&self, text: &str) -> Result<LlmResult, String> {
+        let url = format!("{}/messages", self.api_base.trim_end_matches('/'));
+        let (system, user, prompt) = build_turns(text);
+        let request = MessagesRequest {
+            model: self.model.clone(),
+            system,
+            max_tokens: self.max_tokens,
+            temperature: 0.2,
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: user,
+            }],
+            tools: vec![report_virality_tool()],
+            tool_choice: ToolChoice::force(),
+            stream: None,
+        };
+
+        let started = Instant::now();
+        let response = self
+            .request(&url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|err| format!("Anthropic request failed: {}", err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            let detail = error_body.trim();
+            if detail.is_empty() {
+                return Err(format!("Anthropic API error: {}", status));
+            }
+            return Err(format!("Anthropic API error: {} {}", status, detail));
+        }
+
+        let body: MessagesResponse = response
+            .json()
+            .await
+            .map_err(|err| format!("Anthropic response parse failed: {}", err))?;
+
+        let tool_input = body
+            .content
+            .iter()
+            .find(|block| block.block_type == "tool_use")
+            .and_then(|block| block.input.clone())
+            .ok_or_else(|| "Anthropic response missing report_virality tool call".to_string())?;
+
+        let raw_response =
+            serde_json::to_string(&tool_input).unwrap_or_else(|_| tool_input.to_string());
+        let score = parse_score_value(tool_input)?;
+        let usage = body.usage.unwrap_or_default();
+        let trace = build_trace(
+            body.model.unwrap_or_else(|| self.model.clone()),
+            started,
+            prompt,
+            raw_response,
+            Usage {
+                prompt_tokens: usage.input_tokens,
+                completion_tokens: usage.output_tokens,
+                total_tokens: usage
+                    .input_tokens
+                    .zip(usage.output_tokens)
+                    .map(|(input, output)| input + output),
+            },
+            OUTPUT_MODE_TOOL_CALL,
+        );
+
+        Ok(LlmResult { score, trace })
+    }
+
+    async fn score_text_stream(
+        &self,
+        text: &str,
+        on_token: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<LlmResult, String> {
+        let url = format!("{}/messages", self.api_base.trim_end_matches('/'));
+        let (system, user, prompt) = build_turns(text);
+        let request = MessagesRequest {
+            model: self.model.clone(),
+            system,
+            max_tokens: self.max_tokens,
+            temperature: 0.2,
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: user,
+            }],
+            tools: vec![report_virality_tool()],
+            tool_choice: ToolChoice::force(),
+            stream: Some(true),
+        };
+
+        let started = Instant::now();
+        let response = self
+            .request(&url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|err| format!("Anthropic request failed: {}", err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            let detail = error_body.trim();
+            if detail.is_empty() {
+                return Err(format!("Anthropic API error: {}", status));
+            }
+            return Err(format!("Anthropic API error: {} {}", status, detail));
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut buffer = String::new();
+        let mut tool_json = String::new();
+        let mut model: Option<String> = None;
+        let mut usage = MessagesUsage::default();
+
+        'outer: while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|err| format!("Anthropic stream failed: {}", err))?;
+            let text_chunk = String::from_utf8_lossy(&chunk);
+            buffer.push_str(&text_chunk);
+            if buffer.contains("\r\n") {
+                buffer = buffer.replace("\r\n", "\n");
+            }
+
+            for data in drain_sse_events(&mut buffer) {
+                let event: StreamEvent = match serde_json::from_str(&data) {
+                    Ok(event) => event,
+                    Err(_) => continue,
+                };
+                match event.event_type.as_str() {
+                    "message_start" => {
+                        if let Some(message) = event.message {
+                            model = message.model;
+                            if let Some(message_usage) = message.usage {
+                                usage.input_tokens = message_usage.input_tokens;
+                            }
+                        }
+                    }
+                    "content_block_delta" => {
+                        if let Some(delta) = event.delta.and_then(|delta| delta.partial_json) {
+                            tool_json.push_str(&delta);
+                            on_token(&delta);
+                        }
+                    }
+                    "message_delta" => {
+                        if let Some(delta_usage) = event.usage {
+                            usage.output_tokens = delta_usage.output_tokens;
+                        }
+                    }
+                    "message_stop" => break 'outer,
+                    _ => {}
+                }
+            }
+        }
+
+        let tool_value: Value = serde_json::from_str(&tool_json)
+            .map_err(|err| format!("Anthropic tool-call arguments parse failed: {}", err))?;
+        let score = parse_score_value(tool_value)?;
+        let trace = build_trace(
+            model.unwrap_or_else(|| self.model.clone()),
+            started,
+            prompt,
+            tool_json,
+            Usage {
+                prompt_tokens: usage.input_tokens,
+                completion_tokens: usage.output_tokens,
+                total_tokens: usage
+                    .input_tokens
+                    .zip(usage.output_tokens)
+                    .map(|(input, output)| input + output),
+            },
+            OUTPUT_MODE_TOOL_CALL,
+        );
+
+        Ok(LlmResult { score, trace })
+    }
+}
+
+#[derive(Serialize)]
+struct MessagesRequest {
+    model: String,
+    system: String,
+    max_tokens: u32,
+    temperature: f64,
+    messages: Vec<Message>,
+    tools: Vec<Tool>,
+    tool_choice: ToolChoice,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct Message {
+    role: String,
+    content: String,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    name: String,
+    description: String,
+    input_schema: Value,
+}
+
+fn report_virality_tool() -> Tool {
+    Tool {
+        name: REPORT_VIRALITY_TOOL.to_string(),
+        description: "Report the virality scoring fields for a tweet.".to_string(),
+        input_schema: report_virality_schema(),
+    }
+}
+
+#[derive(Serialize)]
+struct ToolChoice {
+    #[serde(rename = "type")]
+    choice_type: String,
+    name: String,
+}
+
+impl ToolChoice {
+    fn force() -> Self {
+        Self {
+            choice_type: "tool".to_string(),
+            name: REPORT_VIRALITY_TOOL.to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct MessagesResponse {
+    content: Vec<ContentBlock>,
+    model: Option<String>,
+    usage: Option<MessagesUsage>,
+}
+
+#[derive(Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type")]
+    block_type: String,
+    input: Option<Value>,
+}
+
+#[derive(Deserialize, Default)]
+struct MessagesUsage {
+    input_tokens: Option<u32>,
+    output_tokens: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct StreamEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    message: Option<StreamMessage>,
+    delta: Option<StreamDelta>,
+    usage: Option<MessagesUsage>,
+}
+
+#[derive(Deserialize)]
+struct StreamMessage {
+    model: Option<String>,
+    usage: Option<MessagesUsage>,
+}
+
+#[derive(Deserialize)]
+struct StreamDelta {
+    partial_json: Option<String>,
+}
+
+fn build_turns(text: &str) -> (String, String, String) {
+    let system = system_prompt();
+    let user = user_message(text);
+    let prompt = prompt_text(&system, &user);
+    (system, user, prompt)
+}
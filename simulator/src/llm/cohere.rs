@@ -0,0 +1,318 @@
+use async_trait::async_trait;
+use reqwest::header::AUTHORIZATION;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::env;
+use std::time::Instant;
+use tokio_stream::StreamExt;
+
+use super::{
+    build_trace, parse_score, parse_score_value, prompt_text, system_prompt, user_message,
+    LlmResult, ScoringBackend, Usage, OUTPUT_MODE_TEXT, OUTPUT_MODE_TOOL_CALL,
+    REPORT_VIRALITY_TOOL,
+};
+
+/// A Cohere `/v1/chat`-shaped backend. Cohere splits the conversation into
+/// a `message` (the latest user turn), an empty `chat_history` (we only
+/// ever send one turn), and a `preamble` for the system prompt, rather than
+/// an OpenAI-style `messages` array. Streaming responses are
+/// newline-delimited JSON events, not `text/event-stream`. Cohere's API has
+/// no way to force a tool call the way OpenAI/Anthropic do, so a declared
+/// `report_virality` tool is preferred when the model calls it, falling
+/// back to the text-extraction path if it answers in prose instead.
+#[derive(Clone)]
+pub struct CohereBackend {
+    client: reqwest::Client,
+    api_key: String,
+    api_base: String,
+    model: String,
+}
+
+impl CohereBackend {
+    pub fn from_env(model_override: Option<String>) -> Option<Self> {
+        let api_key = env::var("COHERE_API_KEY").ok()?;
+        let api_base =
+            env::var("COHERE_API_BASE").unwrap_or_else(|_| "https://api.cohere.com/v1".to_string());
+        let model = model_override
+            .or_else(|| env::var("COHERE_MODEL").ok())
+            .unwrap_or_else(|| "command-r-plus".to_string());
+        Some(Self::new(api_key, api_base, model))
+    }
+
+    pub fn new(api_key: String, api_base: String, model: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key,
+            api_base,
+            model,
+        }
+    }
+}
+
+#[async_trait]
+impl ScoringBackend for CohereBackend {
+    async fn score_text(&self, text: &str) -> Result<LlmResult, String> {
+        let url = format!("{}/chat", self.api_base.trim_end_matches('/'));
+        let (preamble, message, prompt) = build_turns(text);
+        let request = ChatRequest {
+            model: self.model.clone(),
+            message,
+            preamble,
+            chat_history: Vec::new(),
+            temperature: 0.2,
+            tools: vec![report_virality_tool()],
+            stream: None,
+        };
+
+        let started = Instant::now();
+        let response = self
+            .client
+            .post(url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.api_key))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|err| format!("Cohere request failed: {}", err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            let detail = error_body.trim();
+            if detail.is_empty() {
+                return Err(format!("Cohere API error: {}", status));
+            }
+            return Err(format!("Cohere API error: {} {}", status, detail));
+        }
+
+        let body: ChatResponse = response
+            .json()
+            .await
+            .map_err(|err| format!("Cohere response parse failed: {}", err))?;
+
+        let usage = body.meta.as_ref().and_then(|meta| meta.tokens).unwrap_or_default();
+        let (score, raw_response, output_mode) = parse_chat_response(&body)?;
+        let trace = build_trace(
+            self.model.clone(),
+            started,
+            prompt,
+            raw_response,
+            Usage {
+                prompt_tokens: usage.input_tokens,
+                completion_tokens: usage.output_tokens,
+                total_tokens: usage
+                    .input_tokens
+                    .zip(usage.output_tokens)
+                    .map(|(input, output)| input + output),
+            },
+            output_mode,
+        );
+
+        Ok(LlmResult { score, trace })
+    }
+
+    async fn score_text_stream(
+        &self,
+        text: &str,
+        on_token: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<LlmResult, String> {
+        let url = format!("{}/chat", self.api_base.trim_end_matches('/'));
+        let (preamble, message, prompt) = build_turns(text);
+        let request = ChatRequest {
+            model: self.model.clone(),
+            message,
+            preamble,
+            chat_history: Vec::new(),
+            temperature: 0.2,
+            tools: vec![report_virality_tool()],
+            stream: Some(true),
+        };
+
+        let started = Instant::now();
+        let response = self
+            .client
+            .post(url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.api_key))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|err| format!("Cohere request failed: {}", err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            let detail = error_body.trim();
+            if detail.is_empty() {
+                return Err(format!("Cohere API error: {}", status));
+            }
+            return Err(format!("Cohere API error: {} {}", status, detail));
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut buffer = String::new();
+        let mut content = String::new();
+        let mut final_response: Option<ChatResponse> = None;
+
+        'outer: while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|err| format!("Cohere stream failed: {}", err))?;
+            let text_chunk = String::from_utf8_lossy(&chunk);
+            buffer.push_str(&text_chunk);
+
+            while let Some(idx) = buffer.find('\n') {
+                let line = buffer[..idx].trim().to_string();
+                buffer = buffer[idx + 1..].to_string();
+                if line.is_empty() {
+                    continue;
+                }
+
+                let event: StreamEvent = match serde_json::from_str(&line) {
+                    Ok(event) => event,
+                    Err(_) => continue,
+                };
+                match event.event_type.as_str() {
+                    "text-generation" => {
+                        if let Some(delta) = event.text {
+                            content.push_str(&delta);
+                            on_token(&delta);
+                        }
+                    }
+                    "stream-end" => {
+                        final_response = event.response;
+                        break 'outer;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let usage = final_response
+            .as_ref()
+            .and_then(|response| response.meta.as_ref())
+            .and_then(|meta| meta.tokens)
+            .unwrap_or_default();
+        let (score, raw_response, output_mode) = match final_response {
+            Some(response) => parse_chat_response(&response)?,
+            None => (parse_score(&content)?, content, OUTPUT_MODE_TEXT),
+        };
+        let trace = build_trace(
+            self.model.clone(),
+            started,
+            prompt,
+            raw_response,
+            Usage {
+                prompt_tokens: usage.input_tokens,
+                completion_tokens: usage.output_tokens,
+                total_tokens: usage
+                    .input_tokens
+                    .zip(usage.output_tokens)
+                    .map(|(input, output)| input + output),
+            },
+            output_mode,
+        );
+
+        Ok(LlmResult { score, trace })
+    }
+}
+
+/// Prefers the `report_virality` tool call when Cohere made one, falling
+/// back to brace-matching the plain-text `text` field otherwise.
+fn parse_chat_response(body: &ChatResponse) -> Result<(virality_sim::LlmScore, String, &'static str), String> {
+    if let Some(call) = body
+        .tool_calls
+        .as_ref()
+        .and_then(|calls| calls.iter().find(|call| call.name == REPORT_VIRALITY_TOOL))
+    {
+        let raw_response = serde_json::to_string(&call.parameters)
+            .unwrap_or_else(|_| call.parameters.to_string());
+        let score = parse_score_value(call.parameters.clone())?;
+        return Ok((score, raw_response, OUTPUT_MODE_TOOL_CALL));
+    }
+
+    let content = body.text.trim().to_string();
+    let score = parse_score(&content)?;
+    Ok((score, content, OUTPUT_MODE_TEXT))
+}
+
+#[derive(Serialize)]
+struct ChatRequest {
+    model: String,
+    message: String,
+    preamble: String,
+    chat_history: Vec<ChatHistoryEntry>,
+    temperature: f64,
+    tools: Vec<Tool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct ChatHistoryEntry {
+    role: String,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    name: String,
+    description: String,
+    parameter_definitions: Value,
+}
+
+fn report_virality_tool() -> Tool {
+    Tool {
+        name: REPORT_VIRALITY_TOOL.to_string(),
+        description: "Report the virality scoring fields for a tweet.".to_string(),
+        parameter_definitions: serde_json::json!({
+            "hook": { "description": "Hook strength, 0..1", "type": "float", "required": true },
+            "clarity": { "description": "Clarity, 0..1", "type": "float", "required": true },
+            "novelty": { "description": "Novelty, 0..1", "type": "float", "required": true },
+            "shareability": { "description": "Shareability, 0..1", "type": "float", "required": true },
+            "controversy": { "description": "Controversy, 0..1", "type": "float", "required": true },
+            "sentiment": { "description": "Sentiment, -1..1", "type": "float", "required": true },
+            "suggestions": {
+                "description": "3-5 short, actionable suggestions",
+                "type": "list",
+                "required": true
+            }
+        }),
+    }
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    #[serde(default)]
+    text: String,
+    meta: Option<ChatMeta>,
+    tool_calls: Option<Vec<ToolCall>>,
+}
+
+#[derive(Deserialize)]
+struct ToolCall {
+    name: String,
+    parameters: Value,
+}
+
+#[derive(Deserialize)]
+struct ChatMeta {
+    tokens: Option<ChatUsage>,
+}
+
+#[derive(Deserialize, Default, Clone, Copy)]
+struct ChatUsage {
+    input_tokens: Option<u32>,
+    output_tokens: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct StreamEvent {
+    #[serde(rename = "eventType")]
+    event_type: String,
+    text: Option<String>,
+    response: Option<ChatResponse>,
+}
+
+fn build_turns(text: &str) -> (String, String, String) {
+    let preamble = system_prompt();
+    let message = user_message(text);
+    let prompt = prompt_text(&preamble, &message);
+    (preamble, message, prompt)
+}
@@ -0,0 +1,271 @@
+pub mod anthropic;
+pub mod cohere;
+pub mod ollama;
+pub mod openai_compatible;
+
+use async_trait::async_trait;
+use serde_json::Value;
+use std::env;
+use std::time::Instant;
+use virality_sim::{LlmScore, LlmTrace};
+
+pub use anthropic::AnthropicBackend;
+pub use cohere::CohereBackend;
+pub use ollama::OllamaBackend;
+pub use openai_compatible::OpenAiCompatibleBackend;
+
+#[derive(Clone)]
+pub struct LlmResult {
+    pub score: LlmScore,
+    pub trace: LlmTrace,
+}
+
+/// A model provider the simulator can ask to score a tweet's virality
+/// signals. `OpenAiCompatibleBackend` is the reference implementation
+/// (it covers xAI, OpenAI, and any OpenAI-compatible proxy); the other
+/// backends reuse the same system prompt and `parse_score`/`LlmTrace`
+/// assembly but build their own request/response shapes, since Claude
+/// hoists the system prompt to a top-level field, Cohere splits the
+/// conversation into `message`/`chat_history`, and Ollama speaks
+/// newline-delimited JSON instead of SSE.
+#[async_trait]
+pub trait ScoringBackend: Send + Sync {
+    async fn score_text(&self, text: &str) -> Result<LlmResult, String>;
+
+    async fn score_text_stream(
+        &self,
+        text: &str,
+        on_token: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<LlmResult, String>;
+}
+
+/// Which `ScoringBackend` to build from the environment, selected via
+/// `SCORER_PROVIDER`. Defaults to `openai-compatible` so existing
+/// `XAI_API_KEY` deployments keep working unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScorerProvider {
+    OpenAiCompatible,
+    Anthropic,
+    Cohere,
+    Ollama,
+}
+
+impl ScorerProvider {
+    fn parse(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "xai" | "openai" | "openai-compatible" | "openai_compatible" => {
+                Some(Self::OpenAiCompatible)
+            }
+            "anthropic" | "claude" => Some(Self::Anthropic),
+            "cohere" => Some(Self::Cohere),
+            "ollama" => Some(Self::Ollama),
+            _ => None,
+        }
+    }
+}
+
+/// Builds the `ScoringBackend` selected by `SCORER_PROVIDER` from its
+/// provider-specific environment variables, falling back to `None` if the
+/// selected provider isn't configured (e.g. its API key is unset).
+pub fn from_env(model_override: Option<String>) -> Option<Box<dyn ScoringBackend>> {
+    let provider = env::var("SCORER_PROVIDER")
+        .ok()
+        .and_then(|value| ScorerProvider::parse(&value))
+        .unwrap_or(ScorerProvider::OpenAiCompatible);
+
+    match provider {
+        ScorerProvider::OpenAiCompatible => openai_compatible::OpenAiCompatibleBackend::from_env(
+            model_override,
+        )
+        .map(|backend| Box::new(backend) as Box<dyn ScoringBackend>),
+        ScorerProvider::Anthropic => anthropic::AnthropicBackend::from_env(model_override)
+            .map(|backend| Box::new(backend) as Box<dyn ScoringBackend>),
+        ScorerProvider::Cohere => cohere::CohereBackend::from_env(model_override)
+            .map(|backend| Box::new(backend) as Box<dyn ScoringBackend>),
+        ScorerProvider::Ollama => ollama::OllamaBackend::from_env(model_override)
+            .map(|backend| Box::new(backend) as Box<dyn ScoringBackend>),
+    }
+}
+
+pub(crate) fn system_prompt() -> String {
+    let prompt = r#"You are a strict JSON-only scorer for tweet virality signals.
+Return a single JSON object with these fields:
+- hook (0..1)
+- clarity (0..1)
+- novelty (0..1)
+- shareability (0..1)
+- controversy (0..1)
+- sentiment (-1..1)
+- suggestions (array of 3-5 short, actionable strings)
+Rules:
+- Output JSON only, no markdown or commentary.
+- Use decimals with a leading 0 (e.g., 0.42).
+"#;
+    prompt.to_string()
+}
+
+pub(crate) fn user_message(text: &str) -> String {
+    format!("Tweet:\n{}", text)
+}
+
+pub(crate) fn prompt_summary() -> String {
+    "Scores hook, clarity, novelty, shareability, controversy, sentiment + suggestions."
+        .to_string()
+}
+
+/// Builds the human-readable prompt trace shown in the UI/CLI, independent
+/// of how a given backend actually wire-encodes the system/user turns.
+pub(crate) fn prompt_text(system: &str, user: &str) -> String {
+    format!("System:\n{}\n\nUser:\n{}", system, user)
+}
+
+pub fn prompt_for_text(text: &str) -> String {
+    prompt_text(&system_prompt(), &user_message(text))
+}
+
+/// Name of the tool/function backends that support structured tool calling
+/// are asked to invoke, in place of parsing free-text JSON.
+pub(crate) const REPORT_VIRALITY_TOOL: &str = "report_virality";
+
+pub(crate) const OUTPUT_MODE_JSON_OBJECT: &str = "json_object";
+pub(crate) const OUTPUT_MODE_TOOL_CALL: &str = "tool_call";
+pub(crate) const OUTPUT_MODE_TEXT: &str = "text";
+
+/// JSON-Schema `parameters`/`input_schema` for the `report_virality` tool,
+/// matching `LlmScore` field for field.
+pub(crate) fn report_virality_schema() -> Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "hook": { "type": "number", "minimum": 0, "maximum": 1 },
+            "clarity": { "type": "number", "minimum": 0, "maximum": 1 },
+            "novelty": { "type": "number", "minimum": 0, "maximum": 1 },
+            "shareability": { "type": "number", "minimum": 0, "maximum": 1 },
+            "controversy": { "type": "number", "minimum": 0, "maximum": 1 },
+            "sentiment": { "type": "number", "minimum": -1, "maximum": 1 },
+            "suggestions": {
+                "type": "array",
+                "items": { "type": "string" },
+                "minItems": 3,
+                "maxItems": 5
+            }
+        },
+        "required": [
+            "hook", "clarity", "novelty", "shareability", "controversy", "sentiment", "suggestions"
+        ]
+    })
+}
+
+/// Text-extraction fallback: grabs the first `{`..last `}` span out of free
+/// text, for backends that advertise no structured-output support.
+pub(crate) fn parse_score(content: &str) -> Result<LlmScore, String> {
+    let json = extract_json(content).ok_or_else(|| "model response missing JSON".to_string())?;
+    parse_score_json(&json)
+}
+
+/// Parses a JSON object a backend has already guaranteed is clean (a
+/// `response_format: json_object` body, or a tool call's arguments once
+/// serialized back to a string).
+pub(crate) fn parse_score_json(json: &str) -> Result<LlmScore, String> {
+    let score: LlmScore = serde_json::from_str(json)
+        .map_err(|err| format!("model JSON parse failed: {}", err))?;
+    Ok(clamp_score(score))
+}
+
+/// Parses a tool call's `arguments`/`input` payload straight from its
+/// decoded `serde_json::Value`, skipping the string round-trip.
+pub(crate) fn parse_score_value(value: Value) -> Result<LlmScore, String> {
+    let score: LlmScore = serde_json::from_value(value)
+        .map_err(|err| format!("model tool-call arguments parse failed: {}", err))?;
+    Ok(clamp_score(score))
+}
+
+fn clamp_score(mut score: LlmScore) -> LlmScore {
+    score.hook = clamp01(score.hook);
+    score.clarity = clamp01(score.clarity);
+    score.novelty = clamp01(score.novelty);
+    score.shareability = clamp01(score.shareability);
+    score.controversy = clamp01(score.controversy);
+    score.sentiment = score.sentiment.max(-1.0).min(1.0);
+    score.suggestions = score
+        .suggestions
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .take(6)
+        .collect();
+    score
+}
+
+pub(crate) fn clamp01(value: f64) -> f64 {
+    if value.is_nan() {
+        return 0.0;
+    }
+    value.max(0.0).min(1.0)
+}
+
+pub(crate) fn extract_json(text: &str) -> Option<String> {
+    let start = text.find('{')?;
+    let end = text.rfind('}')?;
+    if start >= end {
+        return None;
+    }
+    Some(text[start..=end].to_string())
+}
+
+/// Splits a buffer of `text/event-stream` data into complete `data:` event
+/// payloads, leaving any trailing partial event in `buffer` for the next
+/// chunk. Shared by every backend whose streaming API is SSE-based
+/// (OpenAI-compatible, Anthropic, Cohere); Ollama uses newline-delimited
+/// JSON instead and has its own draining.
+pub(crate) fn drain_sse_events(buffer: &mut String) -> Vec<String> {
+    let mut events = Vec::new();
+    loop {
+        let Some(idx) = buffer.find("\n\n") else {
+            break;
+        };
+        let block = buffer[..idx].to_string();
+        *buffer = buffer[idx + 2..].to_string();
+        let mut data_lines = Vec::new();
+        for line in block.lines() {
+            let line = line.trim_end_matches('\r');
+            if let Some(data) = line.strip_prefix("data:") {
+                data_lines.push(data.trim_start().to_string());
+            }
+        }
+        if !data_lines.is_empty() {
+            events.push(data_lines.join("\n"));
+        }
+    }
+    events
+}
+
+#[derive(Default, Clone, Copy)]
+pub(crate) struct Usage {
+    pub prompt_tokens: Option<u32>,
+    pub completion_tokens: Option<u32>,
+    pub total_tokens: Option<u32>,
+}
+
+pub(crate) fn build_trace(
+    model: String,
+    started: Instant,
+    prompt: String,
+    content: String,
+    usage: Usage,
+    output_mode: &str,
+) -> LlmTrace {
+    LlmTrace {
+        model,
+        latency_ms: started.elapsed().as_millis(),
+        prompt_summary: prompt_summary(),
+        prompt,
+        raw_response: content,
+        prompt_tokens: usage.prompt_tokens,
+        completion_tokens: usage.completion_tokens,
+        total_tokens: usage.total_tokens,
+        output_mode: output_mode.to_string(),
+        novelty_neighbor_text: None,
+        novelty_neighbor_similarity: None,
+    }
+}
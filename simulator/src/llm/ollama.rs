@@ -0,0 +1,239 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::time::Instant;
+use tokio_stream::StreamExt;
+
+use super::{
+    build_trace, parse_score, prompt_text, system_prompt, user_message, LlmResult, ScoringBackend,
+    Usage, OUTPUT_MODE_TEXT,
+};
+
+/// A local Ollama `/api/chat` backend. No API key: Ollama is addressed by
+/// host/port only, defaulting to the standard `localhost:11434` install.
+/// Like Cohere, its streaming responses are newline-delimited JSON objects
+/// rather than `text/event-stream`, but each line is a self-contained
+/// object (no `eventType` wrapper) with a trailing `done: true` line
+/// carrying the token counts. Ollama models vary widely in tool-calling
+/// support, so this backend sticks to the text-extraction fallback rather
+/// than assuming a schema-constrained mode is available.
+#[derive(Clone)]
+pub struct OllamaBackend {
+    client: reqwest::Client,
+    api_base: String,
+    model: String,
+}
+
+impl OllamaBackend {
+    /// Unlike the other backends, Ollama has no API key to gate on -- it's
+    /// a local server, so selecting the provider via `SCORER_PROVIDER` is
+    /// enough and this always succeeds.
+    pub fn from_env(model_override: Option<String>) -> Option<Self> {
+        let api_base =
+            env::var("OLLAMA_API_BASE").unwrap_or_else(|_| "http://localhost:11434".to_string());
+        let model = model_override
+            .or_else(|| env::var("OLLAMA_MODEL").ok())
+            .unwrap_or_else(|| "llama3".to_string());
+        Some(Self::new(api_base, model))
+    }
+
+    pub fn new(api_base: String, model: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_base,
+            model,
+        }
+    }
+}
+
+#[async_trait]
+impl ScoringBackend for OllamaBackend {
+    async fn score_text(&self, text: &str) -> Result<LlmResult, String> {
+        let url = format!("{}/api/chat", self.api_base.trim_end_matches('/'));
+        let (messages, prompt) = build_messages(text);
+        let request = ChatRequest {
+            model: self.model.clone(),
+            messages,
+            stream: false,
+        };
+
+        let started = Instant::now();
+        let response = self
+            .client
+            .post(url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|err| format!("Ollama request failed: {}", err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            let detail = error_body.trim();
+            if detail.is_empty() {
+                return Err(format!("Ollama API error: {}", status));
+            }
+            return Err(format!("Ollama API error: {} {}", status, detail));
+        }
+
+        let body: ChatLine = response
+            .json()
+            .await
+            .map_err(|err| format!("Ollama response parse failed: {}", err))?;
+
+        let content = body.message.map(|message| message.content).unwrap_or_default();
+        let content = content.trim().to_string();
+        let score = parse_score(&content)?;
+        let trace = build_trace(
+            body.model.unwrap_or_else(|| self.model.clone()),
+            started,
+            prompt,
+            content,
+            Usage {
+                prompt_tokens: body.prompt_eval_count,
+                completion_tokens: body.eval_count,
+                total_tokens: body
+                    .prompt_eval_count
+                    .zip(body.eval_count)
+                    .map(|(input, output)| input + output),
+            },
+            OUTPUT_MODE_TEXT,
+        );
+
+        Ok(LlmResult { score, trace })
+    }
+
+    async fn score_text_stream(
+        &self,
+        text: &str,
+        on_token: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<LlmResult, String> {
+        let url = format!("{}/api/chat", self.api_base.trim_end_matches('/'));
+        let (messages, prompt) = build_messages(text);
+        let request = ChatRequest {
+            model: self.model.clone(),
+            messages,
+            stream: true,
+        };
+
+        let started = Instant::now();
+        let response = self
+            .client
+            .post(url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|err| format!("Ollama request failed: {}", err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            let detail = error_body.trim();
+            if detail.is_empty() {
+                return Err(format!("Ollama API error: {}", status));
+            }
+            return Err(format!("Ollama API error: {} {}", status, detail));
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut buffer = String::new();
+        let mut content = String::new();
+        let mut model: Option<String> = None;
+        let mut prompt_eval_count = None;
+        let mut eval_count = None;
+
+        'outer: while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|err| format!("Ollama stream failed: {}", err))?;
+            let text_chunk = String::from_utf8_lossy(&chunk);
+            buffer.push_str(&text_chunk);
+
+            while let Some(idx) = buffer.find('\n') {
+                let line = buffer[..idx].trim().to_string();
+                buffer = buffer[idx + 1..].to_string();
+                if line.is_empty() {
+                    continue;
+                }
+
+                let event: ChatLine = serde_json::from_str(&line)
+                    .map_err(|err| format!("Ollama stream parse failed: {}", err))?;
+                if let Some(model_value) = event.model {
+                    model = Some(model_value);
+                }
+                if let Some(delta) = event.message.as_ref().map(|message| message.content.as_str()) {
+                    if !delta.is_empty() {
+                        content.push_str(delta);
+                        on_token(delta);
+                    }
+                }
+                if event.done {
+                    prompt_eval_count = event.prompt_eval_count;
+                    eval_count = event.eval_count;
+                    break 'outer;
+                }
+            }
+        }
+
+        let score = parse_score(&content)?;
+        let trace = build_trace(
+            model.unwrap_or_else(|| self.model.clone()),
+            started,
+            prompt,
+            content,
+            Usage {
+                prompt_tokens: prompt_eval_count,
+                completion_tokens: eval_count,
+                total_tokens: prompt_eval_count
+                    .zip(eval_count)
+                    .map(|(input, output)| input + output),
+            },
+            OUTPUT_MODE_TEXT,
+        );
+
+        Ok(LlmResult { score, trace })
+    }
+}
+
+#[derive(Serialize)]
+struct ChatRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    stream: bool,
+}
+
+#[derive(Serialize)]
+struct ChatMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Deserialize, Default)]
+struct ChatLine {
+    model: Option<String>,
+    message: Option<ChatMessageResponse>,
+    #[serde(default)]
+    done: bool,
+    prompt_eval_count: Option<u32>,
+    eval_count: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct ChatMessageResponse {
+    content: String,
+}
+
+fn build_messages(text: &str) -> (Vec<ChatMessage>, String) {
+    let system = system_prompt();
+    let user = user_message(text);
+    let prompt = prompt_text(&system, &user);
+    let messages = vec![
+        ChatMessage {
+            role: "system".to_string(),
+            content: system,
+        },
+        ChatMessage {
+            role: "user".to_string(),
+            content: user,
+        },
+    ];
+    (messages, prompt)
+}
@@ -1,41 +1,64 @@
+use async_trait::async_trait;
 use reqwest::header::AUTHORIZATION;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::time::Instant;
 use tokio_stream::StreamExt;
-use virality_sim::{LlmScore, LlmTrace};
 
+use super::{
+    build_trace, drain_sse_events, parse_score_json, prompt_text, system_prompt, user_message,
+    LlmResult, ScoringBackend, Usage, OUTPUT_MODE_JSON_OBJECT,
+};
+
+/// An OpenAI `/chat/completions`-shaped backend. This is the reference
+/// implementation -- xAI's Grok, OpenAI itself, and most local proxies
+/// (including Ollama's `/v1` compatibility layer) all speak this wire
+/// format, so one struct parameterized by API base/key covers all of them.
+/// `XAI_*` env vars are checked first to keep existing deployments working,
+/// falling back to the generic `OPENAI_*` names.
 #[derive(Clone)]
-pub struct LlmResult {
-    pub score: LlmScore,
-    pub trace: LlmTrace,
-}
-
-#[derive(Clone)]
-pub struct LlmClient {
+pub struct OpenAiCompatibleBackend {
     client: reqwest::Client,
     api_key: String,
     api_base: String,
     model: String,
+    provider_label: &'static str,
 }
 
-impl LlmClient {
+impl OpenAiCompatibleBackend {
     pub fn from_env(model_override: Option<String>) -> Option<Self> {
-        let api_key = env::var("XAI_API_KEY").ok()?;
-        let api_base = env::var("XAI_API_BASE").unwrap_or_else(|_| "https://api.x.ai/v1".to_string());
+        if let Ok(api_key) = env::var("XAI_API_KEY") {
+            let api_base =
+                env::var("XAI_API_BASE").unwrap_or_else(|_| "https://api.x.ai/v1".to_string());
+            let model = model_override
+                .or_else(|| env::var("XAI_MODEL").ok())
+                .unwrap_or_else(|| "grok-2-latest".to_string());
+            return Some(Self::new(api_key, api_base, model, "xAI"));
+        }
+
+        let api_key = env::var("OPENAI_API_KEY").ok()?;
+        let api_base =
+            env::var("OPENAI_API_BASE").unwrap_or_else(|_| "https://api.openai.com/v1".to_string());
         let model = model_override
-            .or_else(|| env::var("XAI_MODEL").ok())
-            .unwrap_or_else(|| "grok-2-latest".to_string());
-        let client = reqwest::Client::new();
-        Some(Self {
-            client,
+            .or_else(|| env::var("OPENAI_MODEL").ok())
+            .unwrap_or_else(|| "gpt-4o-mini".to_string());
+        Some(Self::new(api_key, api_base, model, "OpenAI"))
+    }
+
+    pub fn new(api_key: String, api_base: String, model: String, provider_label: &'static str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
             api_key,
             api_base,
             model,
-        })
+            provider_label,
+        }
     }
+}
 
-    pub async fn score_text(&self, text: &str) -> Result<LlmResult, String> {
+#[async_trait]
+impl ScoringBackend for OpenAiCompatibleBackend {
+    async fn score_text(&self, text: &str) -> Result<LlmResult, String> {
         let url = format!("{}/chat/completions", self.api_base.trim_end_matches('/'));
         let (messages, prompt) = build_messages(text);
         let request = ChatRequest {
@@ -43,6 +66,7 @@ impl LlmClient {
             temperature: 0.2,
             messages,
             stream: None,
+            response_format: Some(ResponseFormat::json_object()),
         };
 
         let started = Instant::now();
@@ -53,59 +77,58 @@ impl LlmClient {
             .json(&request)
             .send()
             .await
-            .map_err(|err| format!("xAI request failed: {}", err))?;
+            .map_err(|err| format!("{} request failed: {}", self.provider_label, err))?;
 
         let status = response.status();
         if !status.is_success() {
-            let error_body = response
-                .text()
-                .await
-                .unwrap_or_else(|_| String::new());
+            let error_body = response.text().await.unwrap_or_default();
             let detail = error_body.trim();
             if detail.is_empty() {
-                return Err(format!("xAI API error: {}", status));
+                return Err(format!("{} API error: {}", self.provider_label, status));
             }
-            return Err(format!("xAI API error: {} {}", status, detail));
+            return Err(format!(
+                "{} API error: {} {}",
+                self.provider_label, status, detail
+            ));
         }
 
         let body: ChatResponse = response
             .json()
             .await
-            .map_err(|err| format!("xAI response parse failed: {}", err))?;
+            .map_err(|err| format!("{} response parse failed: {}", self.provider_label, err))?;
 
         let content = body
             .choices
             .first()
-            .ok_or_else(|| "xAI response missing choices".to_string())?
+            .ok_or_else(|| format!("{} response missing choices", self.provider_label))?
             .message
             .content
             .trim()
             .to_string();
 
-        let score = parse_score(&content)?;
+        let score = parse_score_json(&content)?;
         let usage = body.usage.unwrap_or_default();
-        let trace = LlmTrace {
-            model: body.model.unwrap_or_else(|| self.model.clone()),
-            latency_ms: started.elapsed().as_millis(),
-            prompt_summary: prompt_summary(),
+        let trace = build_trace(
+            body.model.unwrap_or_else(|| self.model.clone()),
+            started,
             prompt,
-            raw_response: content,
-            prompt_tokens: usage.prompt_tokens,
-            completion_tokens: usage.completion_tokens,
-            total_tokens: usage.total_tokens,
-        };
+            content,
+            Usage {
+                prompt_tokens: usage.prompt_tokens,
+                completion_tokens: usage.completion_tokens,
+                total_tokens: usage.total_tokens,
+            },
+            OUTPUT_MODE_JSON_OBJECT,
+        );
 
         Ok(LlmResult { score, trace })
     }
 
-    pub async fn score_text_stream<F>(
+    async fn score_text_stream(
         &self,
         text: &str,
-        mut on_token: F,
-    ) -> Result<LlmResult, String>
-    where
-        F: FnMut(&str) + Send,
-    {
+        on_token: &mut (dyn FnMut(&str) + Send),
+    ) -> Result<LlmResult, String> {
         let url = format!("{}/chat/completions", self.api_base.trim_end_matches('/'));
         let (messages, prompt) = build_messages(text);
         let request = ChatRequest {
@@ -113,6 +136,7 @@ impl LlmClient {
             temperature: 0.2,
             messages,
             stream: Some(true),
+            response_format: Some(ResponseFormat::json_object()),
         };
 
         let started = Instant::now();
@@ -123,19 +147,19 @@ impl LlmClient {
             .json(&request)
             .send()
             .await
-            .map_err(|err| format!("xAI request failed: {}", err))?;
+            .map_err(|err| format!("{} request failed: {}", self.provider_label, err))?;
 
         let status = response.status();
         if !status.is_success() {
-            let error_body = response
-                .text()
-                .await
-                .unwrap_or_else(|_| String::new());
+            let error_body = response.text().await.unwrap_or_default();
             let detail = error_body.trim();
             if detail.is_empty() {
-                return Err(format!("xAI API error: {}", status));
+                return Err(format!("{} API error: {}", self.provider_label, status));
             }
-            return Err(format!("xAI API error: {} {}", status, detail));
+            return Err(format!(
+                "{} API error: {} {}",
+                self.provider_label, status, detail
+            ));
         }
 
         let mut stream = response.bytes_stream();
@@ -145,7 +169,8 @@ impl LlmClient {
         let mut usage = ChatUsage::default();
 
         'outer: while let Some(chunk) = stream.next().await {
-            let chunk = chunk.map_err(|err| format!("xAI stream failed: {}", err))?;
+            let chunk =
+                chunk.map_err(|err| format!("{} stream failed: {}", self.provider_label, err))?;
             let text_chunk = String::from_utf8_lossy(&chunk);
             buffer.push_str(&text_chunk);
             if buffer.contains("\r\n") {
@@ -158,7 +183,7 @@ impl LlmClient {
                 }
 
                 let event: ChatStreamResponse = serde_json::from_str(&data)
-                    .map_err(|err| format!("xAI stream parse failed: {}", err))?;
+                    .map_err(|err| format!("{} stream parse failed: {}", self.provider_label, err))?;
                 if let Some(model_value) = event.model {
                     model = Some(model_value);
                 }
@@ -184,17 +209,19 @@ impl LlmClient {
             }
         }
 
-        let score = parse_score(&content)?;
-        let trace = LlmTrace {
-            model: model.unwrap_or_else(|| self.model.clone()),
-            latency_ms: started.elapsed().as_millis(),
-            prompt_summary: prompt_summary(),
+        let score = parse_score_json(&content)?;
+        let trace = build_trace(
+            model.unwrap_or_else(|| self.model.clone()),
+            started,
             prompt,
-            raw_response: content,
-            prompt_tokens: usage.prompt_tokens,
-            completion_tokens: usage.completion_tokens,
-            total_tokens: usage.total_tokens,
-        };
+            content,
+            Usage {
+                prompt_tokens: usage.prompt_tokens,
+                completion_tokens: usage.completion_tokens,
+                total_tokens: usage.total_tokens,
+            },
+            OUTPUT_MODE_JSON_OBJECT,
+        );
 
         Ok(LlmResult { score, trace })
     }
@@ -207,6 +234,25 @@ struct ChatRequest {
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<ResponseFormat>,
+}
+
+/// Forces a guaranteed-valid JSON body instead of free text, so
+/// `parse_score_json` never needs to brace-match stray characters out of
+/// prose.
+#[derive(Serialize)]
+struct ResponseFormat {
+    #[serde(rename = "type")]
+    format_type: String,
+}
+
+impl ResponseFormat {
+    fn json_object() -> Self {
+        Self {
+            format_type: "json_object".to_string(),
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -257,31 +303,10 @@ struct ChatStreamDelta {
     content: Option<String>,
 }
 
-fn system_prompt() -> String {
-    let prompt = r#"You are a strict JSON-only scorer for tweet virality signals.
-Return a single JSON object with these fields:
-- hook (0..1)
-- clarity (0..1)
-- novelty (0..1)
-- shareability (0..1)
-- controversy (0..1)
-- sentiment (-1..1)
-- suggestions (array of 3-5 short, actionable strings)
-Rules:
-- Output JSON only, no markdown or commentary.
-- Use decimals with a leading 0 (e.g., 0.42).
-"#;
-    prompt.to_string()
-}
-
-fn prompt_summary() -> String {
-    "Scores hook, clarity, novelty, shareability, controversy, sentiment + suggestions.".to_string()
-}
-
 fn build_messages(text: &str) -> (Vec<ChatMessage>, String) {
     let system = system_prompt();
-    let user = format!("Tweet:\n{}", text);
-    let prompt = format!("System:\n{}\n\nUser:\n{}", system, user);
+    let user = user_message(text);
+    let prompt = prompt_text(&system, &user);
     let messages = vec![
         ChatMessage {
             role: "system".to_string(),
@@ -294,68 +319,3 @@ fn build_messages(text: &str) -> (Vec<ChatMessage>, String) {
     ];
     (messages, prompt)
 }
-
-pub fn prompt_for_text(text: &str) -> String {
-    let (_, prompt) = build_messages(text);
-    prompt
-}
-
-fn parse_score(content: &str) -> Result<LlmScore, String> {
-    let json = extract_json(content).ok_or_else(|| "xAI response missing JSON".to_string())?;
-    let mut score: LlmScore = serde_json::from_str(&json)
-        .map_err(|err| format!("xAI JSON parse failed: {}", err))?;
-
-    score.hook = clamp01(score.hook);
-    score.clarity = clamp01(score.clarity);
-    score.novelty = clamp01(score.novelty);
-    score.shareability = clamp01(score.shareability);
-    score.controversy = clamp01(score.controversy);
-    score.sentiment = score.sentiment.max(-1.0).min(1.0);
-    score.suggestions = score
-        .suggestions
-        .into_iter()
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
-        .take(6)
-        .collect();
-
-    Ok(score)
-}
-
-fn drain_sse_events(buffer: &mut String) -> Vec<String> {
-    let mut events = Vec::new();
-    loop {
-        let Some(idx) = buffer.find("\n\n") else {
-            break;
-        };
-        let block = buffer[..idx].to_string();
-        *buffer = buffer[idx + 2..].to_string();
-        let mut data_lines = Vec::new();
-        for line in block.lines() {
-            let line = line.trim_end_matches('\r');
-            if let Some(data) = line.strip_prefix("data:") {
-                data_lines.push(data.trim_start().to_string());
-            }
-        }
-        if !data_lines.is_empty() {
-            events.push(data_lines.join("\n"));
-        }
-    }
-    events
-}
-
-fn extract_json(text: &str) -> Option<String> {
-    let start = text.find('{')?;
-    let end = text.rfind('}')?;
-    if start >= end {
-        return None;
-    }
-    Some(text[start..=end].to_string())
-}
-
-fn clamp01(value: f64) -> f64 {
-    if value.is_nan() {
-        return 0.0;
-    }
-    value.max(0.0).min(1.0)
-}
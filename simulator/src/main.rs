@@ -1,19 +1,32 @@
 mod api;
+mod embeddings;
 mod llm;
+mod openai_compat;
+mod platform;
+mod repository;
 mod server;
 mod snapshots;
+mod token_store;
+mod trends;
 mod x_api;
+mod x_calibration;
+mod x_hydration;
+mod x_stream;
 
 use clap::{Args, Parser, Subcommand};
 use std::io::{self, Read};
 use std::path::Path;
 use virality_sim::{
-    format_float, format_number, format_percent, simulate_with_llm, MediaType, SimulatorInput,
+    format_float, format_number, format_percent, simulate_with_llm, simulate_with_mode, ActionProbs,
+    MediaType, ScoringMode, SimulatorInput,
 };
 
 #[derive(Parser)]
 #[command(name = "virality-sim", about = "Tweet virality simulator")]
 struct Cli {
+    /// Tracing level (e.g. "info", "debug", "virality_sim=debug"). Falls back to RUST_LOG.
+    #[arg(long, global = true)]
+    trace: Option<String>,
     #[command(subcommand)]
     command: Option<Command>,
 }
@@ -22,6 +35,65 @@ struct Cli {
 enum Command {
     Simulate(SimulateArgs),
     Serve(ServeArgs),
+    Calibrate(CalibrateArgs),
+    StreamCalibrate(StreamCalibrateArgs),
+    Backtest(BacktestArgs),
+    Personalize(PersonalizeArgs),
+    Tune(TuneArgs),
+}
+
+#[derive(Args, Debug, Clone)]
+struct CalibrateArgs {
+    #[arg(long)]
+    handle: String,
+    #[arg(long, default_value = "samples.json")]
+    out: String,
+    #[arg(long, default_value_t = 50)]
+    max_posts: usize,
+}
+
+#[derive(Args, Debug, Clone)]
+struct StreamCalibrateArgs {
+    #[arg(long, default_value = "fitted_weights.json")]
+    out: String,
+    #[arg(long, default_value_t = 200)]
+    max_records: usize,
+    #[arg(long, default_value_t = 30)]
+    min_samples: usize,
+    #[arg(long, default_value_t = 1.0)]
+    lambda: f64,
+}
+
+#[derive(Args, Debug, Clone)]
+struct BacktestArgs {
+    /// Path to a calibration-sample JSON file, as produced by `calibrate`.
+    #[arg(long, default_value = "samples.json")]
+    samples: String,
+}
+
+#[derive(Args, Debug, Clone)]
+struct PersonalizeArgs {
+    /// Path to a calibration-sample JSON file, as produced by `calibrate`.
+    #[arg(long, default_value = "samples.json")]
+    samples: String,
+    #[arg(long, default_value_t = 500)]
+    epochs: usize,
+    #[arg(long, default_value_t = 0.05)]
+    learning_rate: f64,
+    #[arg(long, default_value_t = 0.2)]
+    validation_split: f64,
+    #[arg(long, default_value_t = 20)]
+    patience: usize,
+}
+
+#[derive(Args, Debug, Clone)]
+struct TuneArgs {
+    /// Path to the SQLite trace store, as written to by `serve`'s
+    /// `/api/simulate` (`TRACE_DB_PATH`) and `/api/traces/:id/outcome`.
+    #[arg(long, default_value = "data/traces.db")]
+    db: String,
+    #[arg(long, default_value = "fitted_weights.json")]
+    out: String,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -44,6 +116,10 @@ struct SimulateArgs {
     hour: u8,
     #[arg(long, default_value = "none")]
     media: String,
+    #[arg(long)]
+    media_count: Option<u8>,
+    #[arg(long)]
+    live: bool,
     #[arg(long, conflicts_with = "no_link")]
     link: bool,
     #[arg(long, conflicts_with = "link")]
@@ -66,6 +142,14 @@ struct SimulateArgs {
     ai_model: Option<String>,
     #[arg(long)]
     details: bool,
+    #[arg(long, default_value = "heuristic")]
+    mode: String,
+    #[arg(long)]
+    seed: Option<u64>,
+    #[arg(long)]
+    auto_trends: bool,
+    #[arg(long, default_value_t = 1)]
+    trends_woeid: u64,
 }
 
 impl Default for SimulateArgs {
@@ -80,6 +164,8 @@ impl Default for SimulateArgs {
             verified: false,
             hour: 12,
             media: "none".to_string(),
+            media_count: None,
+            live: false,
             link: false,
             no_link: false,
             novelty: 0.5,
@@ -91,6 +177,10 @@ impl Default for SimulateArgs {
             ai: false,
             ai_model: None,
             details: false,
+            mode: "heuristic".to_string(),
+            seed: None,
+            auto_trends: false,
+            trends_woeid: 1,
         }
     }
 }
@@ -103,27 +193,254 @@ pub struct ServeArgs {
     port: u16,
     #[arg(long, default_value = "../webapp/dist")]
     web_root: String,
+    /// Postgres connection string (`postgres://...`) for the shared,
+    /// multi-replica repository backend. Falls back to `DATABASE_URL`, and
+    /// to the original single-node JSON-file repository if neither is set.
+    #[arg(long)]
+    database_url: Option<String>,
 }
 
 #[tokio::main]
 async fn main() {
     load_dotenv();
-    if let Err(err) = run().await {
+    let cli = Cli::parse();
+    init_tracing(cli.trace.as_deref());
+    if let Err(err) = run(cli).await {
         eprintln!("Error: {}", err);
         std::process::exit(1);
     }
 }
 
-async fn run() -> Result<(), String> {
-    let cli = Cli::parse();
+fn init_tracing(trace_level: Option<&str>) {
+    let filter = match trace_level {
+        Some(level) => tracing_subscriber::EnvFilter::new(level),
+        None => tracing_subscriber::EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("warn")),
+    };
+    let subscriber = tracing_subscriber::FmtSubscriber::builder()
+        .with_env_filter(filter)
+        .finish();
+    let _ = tracing::subscriber::set_global_default(subscriber);
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
     let command = cli.command.unwrap_or(Command::Simulate(SimulateArgs::default()));
 
     match command {
         Command::Simulate(args) => run_simulate(args).await,
         Command::Serve(args) => server::serve(args).await,
+        Command::Calibrate(args) => run_calibrate(args).await,
+        Command::StreamCalibrate(args) => run_stream_calibrate(args).await,
+        Command::Backtest(args) => run_backtest(args).await,
+        Command::Personalize(args) => run_personalize(args).await,
+        Command::Tune(args) => run_tune(args).await,
+    }
+}
+
+async fn run_calibrate(args: CalibrateArgs) -> Result<(), String> {
+    let client = x_api::XApiClient::from_env();
+    let samples =
+        x_calibration::build_calibration_corpus(client.as_ref(), &args.handle, args.max_posts)
+            .await?;
+
+    if samples.is_empty() {
+        return Err(format!("no posts found for handle {}", args.handle));
+    }
+
+    let payload = serde_json::to_string_pretty(&samples)
+        .map_err(|err| format!("failed to serialize samples: {}", err))?;
+    std::fs::write(&args.out, payload)
+        .map_err(|err| format!("failed to write {}: {}", args.out, err))?;
+    println!("Wrote {} calibration samples to {}", samples.len(), args.out);
+
+    let config = virality_sim::config::ScoringConfig::load(None)
+        .map(|(config, _, _)| config)
+        .unwrap_or_default();
+    let runner = virality_sim::calibration::CalibrationRunner::new(samples);
+    let metrics = runner.compute_metrics(&config);
+
+    println!(
+        "Impression correlation: {} | Engagement correlation: {}",
+        format_float(metrics.impression_correlation, 3),
+        format_float(metrics.engagement_rate_correlation, 3)
+    );
+    println!(
+        "MAE: like {} | reply {} | repost {}",
+        format_float(metrics.like_rate_mae, 4),
+        format_float(metrics.reply_rate_mae, 4),
+        format_float(metrics.repost_rate_mae, 4)
+    );
+    println!(
+        "Pairwise ranking accuracy: {} ({} samples)",
+        format_float(metrics.pairwise_ranking_accuracy, 3),
+        metrics.sample_count
+    );
+
+    Ok(())
+}
+
+async fn run_stream_calibrate(args: StreamCalibrateArgs) -> Result<(), String> {
+    let client = x_stream::XStreamClient::from_env()
+        .ok_or_else(|| "missing X_API_BEARER_TOKEN for streaming".to_string())?;
+
+    let mut calibrator = virality_sim::scoring::WeightCalibrator::new();
+    client
+        .consume_filtered_stream(args.max_records, |record| {
+            let outcome = (record.like_count + record.reply_count + record.repost_count + record.quote_count)
+                as f64;
+            let impressions = record
+                .impression_count
+                .unwrap_or((outcome.max(1.0) as u64) * 20)
+                .max(1) as f64;
+
+            let mut actions = ActionProbs::default();
+            actions.like = record.like_count as f64 / impressions;
+            actions.reply = record.reply_count as f64 / impressions;
+            actions.repost = record.repost_count as f64 / impressions;
+            actions.quote = record.quote_count as f64 / impressions;
+
+            calibrator.record(actions, outcome);
+        })
+        .await?;
+
+    let fit = calibrator.fit(args.lambda, args.min_samples);
+    if !fit.reliable {
+        eprintln!(
+            "warning: fit from only {} samples (want >= {}); weights may be unreliable",
+            fit.sample_count, args.min_samples
+        );
+    }
+
+    let payload = serde_json::to_string_pretty(&fit.weights)
+        .map_err(|err| format!("failed to serialize fitted weights: {}", err))?;
+    std::fs::write(&args.out, payload)
+        .map_err(|err| format!("failed to write {}: {}", args.out, err))?;
+    println!(
+        "Fitted weights from {} samples written to {}",
+        fit.sample_count, args.out
+    );
+
+    Ok(())
+}
+
+async fn run_backtest(args: BacktestArgs) -> Result<(), String> {
+    let contents = std::fs::read_to_string(&args.samples)
+        .map_err(|err| format!("failed to read {}: {}", args.samples, err))?;
+    let samples: Vec<virality_sim::calibration::CalibrationSample> = serde_json::from_str(&contents)
+        .map_err(|err| format!("failed to parse {}: {}", args.samples, err))?;
+
+    let config = virality_sim::config::ScoringConfig::load(None)
+        .map(|(config, _, _)| config)
+        .unwrap_or_default();
+    let report = virality_sim::calibration::BacktestRunner::new(samples).run(&config);
+
+    println!(
+        "Spearman correlation: {} | Engagement-rate MAE: {} ({} samples)",
+        format_float(report.spearman_correlation, 3),
+        format_float(report.engagement_rate_mae, 4),
+        report.sample_count
+    );
+    for tier in &report.tiers {
+        println!(
+            "  {}: observed {} | predicted {} ({} samples)",
+            tier.tier,
+            format_float(tier.observed_rate, 4),
+            format_float(tier.predicted_rate, 4),
+            tier.sample_count
+        );
+    }
+
+    Ok(())
+}
+
+async fn run_personalize(args: PersonalizeArgs) -> Result<(), String> {
+    let contents = std::fs::read_to_string(&args.samples)
+        .map_err(|err| format!("failed to read {}: {}", args.samples, err))?;
+    let samples: Vec<virality_sim::calibration::CalibrationSample> = serde_json::from_str(&contents)
+        .map_err(|err| format!("failed to parse {}: {}", args.samples, err))?;
+
+    let (mut config, config_path, migrations) = virality_sim::config::ScoringConfig::load(None)?;
+    for migration in &migrations {
+        println!(
+            "Migrated scoring config v{} -> v{}: {}",
+            migration.from_version, migration.to_version, migration.description
+        );
+    }
+    let training_config = virality_sim::calibration::PersonalizationTrainingConfig {
+        epochs: args.epochs,
+        learning_rate: args.learning_rate,
+        validation_split: args.validation_split,
+        patience: args.patience,
+    };
+    let report = virality_sim::calibration::PersonalizationTrainer::new(samples)
+        .fit(&config, &training_config);
+
+    println!(
+        "Fitted from {} samples over {} epochs | train MSE {} | validation MSE {}",
+        report.sample_count,
+        report.epochs_run,
+        format_float(report.train_loss, 5),
+        format_float(report.validation_loss, 5)
+    );
+    println!(
+        "Blend weights: hook {} | clarity {} | novelty {} | controversy {} | sentiment {} | shareability {}",
+        format_float(report.blend.hook_weight, 3),
+        format_float(report.blend.clarity_weight, 3),
+        format_float(report.blend.novelty_weight, 3),
+        format_float(report.blend.controversy_weight, 3),
+        format_float(report.blend.sentiment_weight, 3),
+        format_float(report.blend.shareability_weight, 3)
+    );
+    println!(
+        "Tier cutoffs: moderate {} | high {} | very high {} | breakout {}",
+        format_float(report.tiers.moderate, 1),
+        format_float(report.tiers.high, 1),
+        format_float(report.tiers.very_high, 1),
+        format_float(report.tiers.breakout, 1)
+    );
+
+    config.llm_blend = report.blend;
+    config.tier_thresholds = report.tiers;
+    let path = config_path.unwrap_or_else(|| Path::new("config/scoring.toml").to_path_buf());
+    config.write(&path)?;
+    println!("Saved personalized config to {}", path.display());
+
+    Ok(())
+}
+
+async fn run_tune(args: TuneArgs) -> Result<(), String> {
+    let store = virality_sim::calibration::TraceStore::open(Path::new(&args.db).to_path_buf()).await?;
+    let samples = store.load_calibration_samples().await?;
+    if samples.is_empty() {
+        return Err(format!(
+            "no traces with recorded outcomes in {}; report outcomes via /api/traces/:id/outcome first",
+            args.db
+        ));
     }
+
+    let config = virality_sim::config::ScoringConfig::load(None)
+        .map(|(config, _, _)| config)
+        .unwrap_or_default();
+    let fitted = virality_sim::calibration::WeightTuner::new(samples.clone())
+        .tune(config.weights.clone(), &config);
+
+    let payload = serde_json::to_string_pretty(&fitted)
+        .map_err(|err| format!("failed to serialize fitted weights: {}", err))?;
+    std::fs::write(&args.out, payload)
+        .map_err(|err| format!("failed to write {}: {}", args.out, err))?;
+    println!(
+        "Tuned weights from {} stored traces written to {}",
+        samples.len(),
+        args.out
+    );
+
+    Ok(())
 }
 
+#[tracing::instrument(
+    skip(args),
+    fields(followers = args.followers, media = %args.media, mode = %args.mode, score = tracing::field::Empty)
+)]
 async fn run_simulate(args: SimulateArgs) -> Result<(), String> {
     let mut input = SimulatorInput::default();
     input.followers = args.followers;
@@ -149,23 +466,61 @@ async fn run_simulate(args: SimulateArgs) -> Result<(), String> {
 
     input.media = MediaType::from_str(&args.media)
         .ok_or_else(|| format!("invalid media type: {}", args.media))?;
+    if let MediaType::Carousel { count } = &mut input.media {
+        if let Some(media_count) = args.media_count {
+            *count = media_count;
+        }
+    }
+    if let MediaType::Video { is_live } = &mut input.media {
+        *is_live = args.live;
+    }
 
     let text = read_text(args.text)?;
     input.text = text;
 
+    let auto_trend_signals = if args.auto_trends {
+        let client = x_api::XApiClient::from_env();
+        let trend_feed = trends::fetch_trends(client.as_ref(), args.trends_woeid).await;
+        let signals = trends::compute_auto_signals(&input.text, &trend_feed);
+        if !signals.matched.is_empty() {
+            input.timeliness = signals.timeliness;
+            input.topic_saturation = signals.topic_saturation;
+        }
+        Some(signals)
+    } else {
+        None
+    };
+
     let llm_result = if args.ai {
-        let client = llm::LlmClient::from_env(args.ai_model)
-            .ok_or_else(|| "XAI_API_KEY is not set".to_string())?;
-        Some(client.score_text(&input.text).await?)
+        let backend = llm::from_env(args.ai_model)
+            .ok_or_else(|| "no scoring backend configured for SCORER_PROVIDER".to_string())?;
+        Some(backend.score_text(&input.text).await?)
     } else {
         None
     };
 
-    let output = simulate_with_llm(
-        &input,
-        llm_result.as_ref().map(|result| &result.score),
-        llm_result.as_ref().map(|result| &result.trace),
-    );
+    let output = if args.mode.eq_ignore_ascii_case("cascade") {
+        let config = virality_sim::config::ScoringConfig::load(None)
+            .map(|(config, _, _)| config)
+            .unwrap_or_default();
+        let seed = args.seed.unwrap_or(42);
+        simulate_with_mode(
+            &input,
+            llm_result.as_ref().map(|result| &result.score),
+            llm_result.as_ref().map(|result| &result.trace),
+            ScoringMode::Cascade { seed },
+            None,
+            &config,
+        )
+    } else {
+        simulate_with_llm(
+            &input,
+            llm_result.as_ref().map(|result| &result.score),
+            llm_result.as_ref().map(|result| &result.trace),
+        )
+    };
+
+    tracing::Span::current().record("score", output.score);
 
     println!(
         "Virality score: {} ({})",
@@ -190,6 +545,41 @@ async fn run_simulate(args: SimulateArgs) -> Result<(), String> {
         format_float(output.action_volume_rate, 2)
     );
 
+    if args.mode.eq_ignore_ascii_case("cascade") {
+        println!(
+            "Cascade depth: {} | reproduction number: {}",
+            output.cascade_depth,
+            format_float(output.reproduction_number, 2)
+        );
+    }
+
+    if args.mode.eq_ignore_ascii_case("monte-carlo") {
+        let distribution = virality_sim::monte_carlo::simulate_reach_distribution(
+            &output.actions,
+            output.impressions_in.round().max(1.0) as u64,
+            output.final_score,
+            &virality_sim::monte_carlo::MonteCarloConfig::default(),
+            &config.tier_thresholds,
+            args.seed.unwrap_or(42),
+        );
+        println!(
+            "Reach (p10/p50/p90): {} / {} / {}",
+            format_number(distribution.p10_reach),
+            format_number(distribution.p50_reach),
+            format_number(distribution.p90_reach)
+        );
+        println!(
+            "Engaged users (p10/p50/p90): {} / {} / {}",
+            format_number(distribution.p10_engaged_users),
+            format_number(distribution.p50_engaged_users),
+            format_number(distribution.p90_engaged_users)
+        );
+        println!(
+            "Breakout probability: {}",
+            format_percent(distribution.breakout_probability)
+        );
+    }
+
     let likes = output.impressions_total * output.actions.like;
     let replies = output.impressions_total * output.actions.reply;
     let reposts = output.impressions_total * output.actions.repost;
@@ -241,6 +631,20 @@ async fn run_simulate(args: SimulateArgs) -> Result<(), String> {
         println!("  mute: {}", format_percent(output.actions.mute));
         println!("  block: {}", format_percent(output.actions.block));
         println!("  report: {}", format_percent(output.actions.report));
+        println!("  hide_post: {}", format_percent(output.actions.hide_post));
+    }
+
+    if let Some(signals) = auto_trend_signals {
+        if signals.matched.is_empty() {
+            println!("\nTrends: no match found, kept manual timeliness/topic_saturation");
+        } else {
+            println!(
+                "\nTrends: riding {} (timeliness {} | saturation {})",
+                signals.matched.join(", "),
+                format_float(signals.timeliness, 2),
+                format_float(signals.topic_saturation, 2)
+            );
+        }
     }
 
     if !output.suggestions.is_empty() {
@@ -0,0 +1,196 @@
+use std::collections::VecDeque;
+
+use rand::distributions::{Bernoulli, Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::config::TierThresholds;
+use crate::{log10_safe, sigmoid, tier_from_score, ActionProbs, ViralityTier};
+
+/// Tunables for the Monte Carlo reach-distribution simulation.
+#[derive(Debug, Clone)]
+pub struct MonteCarloConfig {
+    pub trials: usize,
+    pub max_depth: u32,
+    pub max_impressions_per_trial: usize,
+    pub follower_reach_per_repost: f64,
+    pub share_amplification: f64,
+    pub hop_decay: f64,
+}
+
+impl Default for MonteCarloConfig {
+    fn default() -> Self {
+        Self {
+            trials: 2_000,
+            max_depth: 12,
+            max_impressions_per_trial: 200_000,
+            follower_reach_per_repost: 250.0,
+            share_amplification: 1.0,
+            hop_decay: 0.7,
+        }
+    }
+}
+
+/// Empirical reach/engagement percentiles across `MonteCarloConfig::trials`
+/// seeded branching-process trials, plus the fraction of trials whose
+/// resulting score (see `tier_from_score`) lands in `ViralityTier::Breakout`.
+#[derive(Debug, Clone)]
+pub struct ReachDistribution {
+    pub p10_reach: f64,
+    pub p50_reach: f64,
+    pub p90_reach: f64,
+    pub p10_engaged_users: f64,
+    pub p50_engaged_users: f64,
+    pub p90_engaged_users: f64,
+    pub breakout_probability: f64,
+}
+
+struct Impression {
+    depth: u32,
+    weight: f64,
+}
+
+/// Named positive actions, in the order their probabilities are passed to
+/// `WeightedIndex` so a sampled index can be mapped back to "did this
+/// impression spread the post further".
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PositiveAction {
+    Like,
+    Reply,
+    Repost,
+    Quote,
+    Share,
+    Click,
+    FollowAuthor,
+}
+
+impl PositiveAction {
+    fn spreads(self) -> bool {
+        matches!(self, PositiveAction::Repost | PositiveAction::Quote | PositiveAction::Share)
+    }
+}
+
+fn weighted_positive_actions(actions: &ActionProbs) -> Vec<(PositiveAction, f64)> {
+    vec![
+        (PositiveAction::Like, actions.like),
+        (PositiveAction::Reply, actions.reply),
+        (PositiveAction::Repost, actions.repost),
+        (PositiveAction::Quote, actions.quote),
+        (PositiveAction::Share, actions.share),
+        (PositiveAction::Click, actions.click),
+        (PositiveAction::FollowAuthor, actions.follow_author),
+    ]
+}
+
+/// Samples `config.trials` independent branching-process cascades seeded
+/// from `seed_impressions` initial views, and returns the empirical
+/// distribution of total reach and engaged users rather than
+/// `unique_engagement_rate`/`action_volume_rate`'s single point estimate.
+///
+/// For each impression, a `Bernoulli` draw over the combined positive-action
+/// probability decides whether it engages at all; a `WeightedIndex` over the
+/// per-action probabilities then decides which action it was, so a
+/// repost/quote/share enqueues `follower_reach_per_repost *
+/// share_amplification`, decayed by `hop_decay` per hop, new impressions.
+/// `baseline_score` is the candidate's deterministic `final_score` (as
+/// produced by the heuristic pipeline); it anchors each trial's score so
+/// `breakout_probability` is measured against the same
+/// `tier_from_score` thresholds the rest of the crate uses.
+pub fn simulate_reach_distribution(
+    actions: &ActionProbs,
+    seed_impressions: u64,
+    baseline_score: f64,
+    config: &MonteCarloConfig,
+    tier_thresholds: &TierThresholds,
+    seed: u64,
+) -> ReachDistribution {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let weighted = weighted_positive_actions(actions);
+    let total_positive = weighted.iter().map(|(_, p)| p.max(0.0)).sum::<f64>();
+    let engage_probability = total_positive.min(1.0);
+    let engage_dist = Bernoulli::new(engage_probability).unwrap_or(Bernoulli::new(0.0).unwrap());
+    let action_dist = WeightedIndex::new(weighted.iter().map(|(_, p)| p.max(f64::EPSILON)))
+        .expect("at least one positive action weight");
+
+    let mut reach_samples = Vec::with_capacity(config.trials);
+    let mut engaged_samples = Vec::with_capacity(config.trials);
+    let mut breakouts = 0usize;
+
+    for _ in 0..config.trials {
+        let mut queue: VecDeque<Impression> = VecDeque::new();
+        for _ in 0..seed_impressions {
+            queue.push_back(Impression { depth: 0, weight: 1.0 });
+        }
+
+        let mut total_reach = 0.0;
+        let mut engaged_users = 0.0;
+        let mut processed = 0usize;
+
+        while let Some(impression) = queue.pop_front() {
+            if processed >= config.max_impressions_per_trial {
+                break;
+            }
+            processed += 1;
+            total_reach += impression.weight;
+
+            if impression.depth > config.max_depth || !engage_dist.sample(&mut rng) {
+                continue;
+            }
+            engaged_users += impression.weight;
+
+            let action = weighted[action_dist.sample(&mut rng)].0;
+            if !action.spreads() {
+                continue;
+            }
+
+            let decay = config.hop_decay.powi(impression.depth as i32 + 1);
+            let child_reach =
+                config.follower_reach_per_repost * config.share_amplification * decay;
+            if child_reach < 1.0 {
+                continue;
+            }
+            queue.push_back(Impression {
+                depth: impression.depth + 1,
+                weight: child_reach,
+            });
+        }
+
+        if tier_for_trial(baseline_score, total_reach, tier_thresholds) == ViralityTier::Breakout {
+            breakouts += 1;
+        }
+        reach_samples.push(total_reach);
+        engaged_samples.push(engaged_users);
+    }
+
+    reach_samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    engaged_samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    ReachDistribution {
+        p10_reach: percentile(&reach_samples, 0.10),
+        p50_reach: percentile(&reach_samples, 0.50),
+        p90_reach: percentile(&reach_samples, 0.90),
+        p10_engaged_users: percentile(&engaged_samples, 0.10),
+        p50_engaged_users: percentile(&engaged_samples, 0.50),
+        p90_engaged_users: percentile(&engaged_samples, 0.90),
+        breakout_probability: breakouts as f64 / config.trials.max(1) as f64,
+    }
+}
+
+/// Mirrors the `score`/`tier_from_score` derivation in `lib.rs` so a trial's
+/// sampled reach maps onto the same 0-100 scale and tier boundaries.
+fn tier_for_trial(
+    baseline_score: f64,
+    impressions_total: f64,
+    tier_thresholds: &TierThresholds,
+) -> ViralityTier {
+    let raw = (baseline_score - 1.0) * 0.8 + (log10_safe(impressions_total + 1.0) - 3.0) * 0.4;
+    tier_from_score(100.0 * sigmoid(raw), tier_thresholds)
+}
+
+fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (fraction * (sorted.len() - 1) as f64).round() as usize;
+    sorted[rank.min(sorted.len() - 1)]
+}
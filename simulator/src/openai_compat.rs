@@ -0,0 +1,165 @@
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use virality_sim::LlmTrace;
+
+/// Wire types for the OpenAI-compatible `/v1/chat/completions` and
+/// `/v1/completions` endpoints served by `server::serve`. Lets existing
+/// OpenAI-client tooling (CLIs, LangChain-style callers, any gateway that
+/// already speaks this protocol) use the virality scorer as a drop-in model
+/// without linking this crate: the last user message (or the `prompt`, for
+/// the legacy completions shape) is treated as the tweet to score, and the
+/// `LlmScore` JSON comes back as assistant content with `usage` assembled
+/// from the backend's own token counts via `usage_from_trace`.
+#[derive(Debug, Deserialize)]
+pub struct ChatCompletionRequest {
+    pub model: Option<String>,
+    pub messages: Vec<ChatMessage>,
+    pub stream: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+/// The legacy `/v1/completions` shape. `prompt` accepts either a single
+/// string or an array; only the first element is scored, matching how most
+/// callers use it for a single input.
+#[derive(Debug, Deserialize)]
+pub struct CompletionRequest {
+    pub model: Option<String>,
+    pub prompt: CompletionPrompt,
+    pub stream: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum CompletionPrompt {
+    Single(String),
+    Many(Vec<String>),
+}
+
+impl CompletionPrompt {
+    pub fn first(&self) -> Option<&str> {
+        match self {
+            CompletionPrompt::Single(text) => Some(text.as_str()),
+            CompletionPrompt::Many(texts) => texts.first().map(String::as_str),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChatCompletionResponse {
+    pub id: String,
+    pub object: &'static str,
+    pub created: u64,
+    pub model: String,
+    pub choices: Vec<ChatCompletionChoice>,
+    pub usage: ChatCompletionUsage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChatCompletionChoice {
+    pub index: u32,
+    pub message: ChatMessage,
+    pub finish_reason: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompletionResponse {
+    pub id: String,
+    pub object: &'static str,
+    pub created: u64,
+    pub model: String,
+    pub choices: Vec<CompletionChoice>,
+    pub usage: ChatCompletionUsage,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CompletionChoice {
+    pub index: u32,
+    pub text: String,
+    pub finish_reason: &'static str,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ChatCompletionUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub total_tokens: u32,
+}
+
+/// One `data:` payload of a `/v1/chat/completions` SSE stream (`stream:
+/// true`). The first chunk for a response carries `delta.role`, subsequent
+/// ones carry `delta.content`, and the last carries `finish_reason: "stop"`
+/// with an empty delta, matching the OpenAI streaming protocol byte-for-byte.
+#[derive(Debug, Serialize)]
+pub struct ChatCompletionChunk {
+    pub id: String,
+    pub object: &'static str,
+    pub created: u64,
+    pub model: String,
+    pub choices: Vec<ChatCompletionChunkChoice>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChatCompletionChunkChoice {
+    pub index: u32,
+    pub delta: ChatCompletionDelta,
+    pub finish_reason: Option<&'static str>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ChatCompletionDelta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OpenAiError {
+    error: OpenAiErrorBody,
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAiErrorBody {
+    message: String,
+    #[serde(rename = "type")]
+    error_type: &'static str,
+}
+
+pub fn openai_error(status: StatusCode, message: &str) -> (StatusCode, Json<OpenAiError>) {
+    (
+        status,
+        Json(OpenAiError {
+            error: OpenAiErrorBody {
+                message: message.to_string(),
+                error_type: "invalid_request_error",
+            },
+        }),
+    )
+}
+
+pub fn usage_from_trace(trace: &LlmTrace) -> ChatCompletionUsage {
+    ChatCompletionUsage {
+        prompt_tokens: trace.prompt_tokens.unwrap_or_default(),
+        completion_tokens: trace.completion_tokens.unwrap_or_default(),
+        total_tokens: trace.total_tokens.unwrap_or_default(),
+    }
+}
+
+pub fn completion_id(prefix: &str) -> String {
+    format!("{}-{}", prefix, now_unix())
+}
+
+pub fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::config::ScoringConfig;
+use crate::ids::{AuthorId, PostId, UserId};
 use crate::ActionProbs;
 
 #[derive(Clone)]
@@ -12,8 +13,8 @@ pub struct PhoenixClient {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct PostFeatures {
-    pub post_id: String,
-    pub author_id: String,
+    pub post_id: PostId,
+    pub author_id: AuthorId,
     pub text_hash: u64,
     pub author_hash: u64,
     pub product_surface: i32,
@@ -22,7 +23,7 @@ pub struct PostFeatures {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct RankingRequest {
-    pub user_id: String,
+    pub user_id: UserId,
     pub user_embedding: Option<Vec<f32>>,
     pub history_posts: Vec<PostFeatures>,
     pub history_actions: Vec<Vec<f32>>,
@@ -31,7 +32,7 @@ pub struct RankingRequest {
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct CandidateScore {
-    pub post_id: String,
+    pub post_id: PostId,
     pub phoenix_scores: ActionProbs,
     pub weighted_score: f64,
     pub rank: usize,
@@ -56,25 +57,46 @@ impl PhoenixClient {
         Ok(Self { endpoint, client })
     }
 
+    #[tracing::instrument(
+        skip(self, request),
+        fields(
+            endpoint = %self.endpoint,
+            candidate_count = request.candidates.len(),
+            status = tracing::field::Empty,
+            latency_ms = tracing::field::Empty,
+        )
+    )]
     pub async fn score(&self, request: RankingRequest) -> Result<RankingResponse, String> {
         let url = format!("{}/rank", self.endpoint.trim_end_matches('/'));
+        let started_at = Instant::now();
         let response = self
             .client
             .post(url)
             .json(&request)
             .send()
             .await
-            .map_err(|err| format!("phoenix request failed: {}", err))?;
+            .map_err(|err| {
+                tracing::error!(error = %err, "phoenix request failed");
+                format!("phoenix request failed: {}", err)
+            })?;
 
-        if !response.status().is_success() {
-            let status = response.status();
+        let status = response.status();
+        let latency_ms = started_at.elapsed().as_millis();
+        tracing::Span::current().record("status", status.as_u16());
+        tracing::Span::current().record("latency_ms", latency_ms);
+
+        if !status.is_success() {
             let body = response.text().await.unwrap_or_default();
+            tracing::warn!(%status, latency_ms, "phoenix returned non-success status");
             return Err(format!("phoenix error {}: {}", status, body));
         }
 
-        response
-            .json::<RankingResponse>()
-            .await
-            .map_err(|err| format!("phoenix response parse failed: {}", err))
+        let parsed = response.json::<RankingResponse>().await.map_err(|err| {
+            tracing::error!(error = %err, "phoenix response parse failed");
+            format!("phoenix response parse failed: {}", err)
+        })?;
+
+        tracing::debug!(latency_ms, scores = parsed.scores.len(), "phoenix score complete");
+        Ok(parsed)
     }
 }
@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+use reqwest::header::AUTHORIZATION;
+use serde::Deserialize;
+use std::env;
+
+use crate::platform::{PlatformPostMetrics, PlatformProfile, SocialPlatform};
+
+/// A client for a single Mastodon (or other ActivityPub-compatible) instance.
+/// Authenticates with a user-issued OAuth2 bearer token, the same way
+/// `XApiClient`'s plain `Bearer` variant does, just scoped to one instance
+/// base URL instead of `api.twitter.com`.
+#[derive(Clone)]
+pub struct MastodonClient {
+    client: reqwest::Client,
+    instance_base: String,
+    access_token: String,
+}
+
+impl MastodonClient {
+    pub fn from_env() -> Option<Self> {
+        let instance_base = env::var("MASTODON_INSTANCE_BASE").ok()?;
+        let access_token = env::var("MASTODON_ACCESS_TOKEN").ok()?;
+        Some(Self::new(instance_base, access_token))
+    }
+
+    pub fn new(instance_base: String, access_token: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            instance_base,
+            access_token,
+        }
+    }
+}
+
+#[async_trait]
+impl SocialPlatform for MastodonClient {
+    async fn fetch_user(&self, handle: &str) -> Result<PlatformProfile, String> {
+        let url = format!(
+            "{}/api/v1/accounts/lookup",
+            self.instance_base.trim_end_matches('/')
+        );
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("acct", handle.trim_start_matches('@'))])
+            .header(AUTHORIZATION, format!("Bearer {}", self.access_token))
+            .send()
+            .await
+            .map_err(|err| format!("Mastodon request failed: {}", err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Mastodon error: {} {}", status, body.trim()));
+        }
+
+        let account: MastodonAccount = response
+            .json()
+            .await
+            .map_err(|err| format!("Mastodon response parse failed: {}", err))?;
+
+        Ok(PlatformProfile {
+            id: account.id,
+            followers: account.followers_count,
+            following: account.following_count,
+            posts_count: Some(account.statuses_count),
+            created_at: account.created_at,
+            verified: None,
+        })
+    }
+
+    async fn fetch_post_metrics(&self, post_id: &str) -> Result<PlatformPostMetrics, String> {
+        let url = format!(
+            "{}/api/v1/statuses/{}",
+            self.instance_base.trim_end_matches('/'),
+            post_id
+        );
+        let response = self
+            .client
+            .get(&url)
+            .header(AUTHORIZATION, format!("Bearer {}", self.access_token))
+            .send()
+            .await
+            .map_err(|err| format!("Mastodon request failed: {}", err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Mastodon error: {} {}", status, body.trim()));
+        }
+
+        let payload: MastodonStatus = response
+            .json()
+            .await
+            .map_err(|err| format!("Mastodon response parse failed: {}", err))?;
+
+        Ok(PlatformPostMetrics {
+            likes: payload.favourites_count,
+            reposts: payload.reblogs_count,
+            replies: payload.replies_count,
+            quotes: None,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct MastodonAccount {
+    id: String,
+    followers_count: u64,
+    following_count: u64,
+    statuses_count: u64,
+    created_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MastodonStatus {
+    favourites_count: u64,
+    reblogs_count: u64,
+    replies_count: u64,
+}
@@ -0,0 +1,63 @@
+pub mod mastodon;
+
+use async_trait::async_trait;
+
+pub use mastodon::MastodonClient;
+
+/// A normalized user/account profile, enough to populate the audience-size
+/// fields `SimulatorInput` needs regardless of which network it came from.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct PlatformProfile {
+    pub id: String,
+    pub followers: u64,
+    pub following: u64,
+    pub posts_count: Option<u64>,
+    pub created_at: Option<String>,
+    pub verified: Option<bool>,
+}
+
+/// A normalized post's engagement counts.
+#[derive(Debug, Clone)]
+pub struct PlatformPostMetrics {
+    pub likes: u64,
+    pub reposts: u64,
+    pub replies: u64,
+    pub quotes: Option<u64>,
+}
+
+/// A social network backend the simulator can resolve an author/post
+/// against. `XApiClient` is the reference implementation; `MastodonClient`
+/// generalizes the same shape to ActivityPub instances, so the rest of the
+/// scoring pipeline (which only cares about the normalized `SimulatorInput`
+/// fields) doesn't need to know which network a candidate came from.
+#[async_trait]
+pub trait SocialPlatform: Send + Sync {
+    async fn fetch_user(&self, handle: &str) -> Result<PlatformProfile, String>;
+    async fn fetch_post_metrics(&self, post_id: &str) -> Result<PlatformPostMetrics, String>;
+}
+
+/// Applies a resolved profile's audience-size fields onto a `SimulatorInput`,
+/// the same way `ApiSimulationRequest::into_input` applies explicit
+/// overrides. Posts-per-day is approximated from `posts_count` and account
+/// age when both are known.
+pub fn apply_profile(input: &mut virality_sim::SimulatorInput, profile: &PlatformProfile) {
+    input.followers = profile.followers;
+    input.following = profile.following;
+    if let Some(verified) = profile.verified {
+        input.verified = verified;
+    }
+}
+
+/// Applies a resolved post's engagement counts as an average-engagement-rate
+/// estimate, the same `(likes + reposts + replies) / followers` shape the X
+/// calibration path uses.
+pub fn apply_post_metrics(
+    input: &mut virality_sim::SimulatorInput,
+    metrics: &PlatformPostMetrics,
+) {
+    if input.followers == 0 {
+        return;
+    }
+    let total = metrics.likes + metrics.reposts + metrics.replies + metrics.quotes.unwrap_or(0);
+    input.avg_engagement_rate = total as f64 / input.followers as f64;
+}
@@ -0,0 +1,348 @@
+use crate::config::ScoringConfig;
+use crate::{extract_text_features, MediaType, SimulationOutput, SimulatorInput, TextFeatures};
+
+/// A ranking DSL over batches of `SimulationOutput`, e.g.
+/// `sort:final_score desc media:video min_score:60 exclude:link has:question`.
+#[derive(Debug, Clone)]
+pub struct Query {
+    pub filters: Vec<Filter>,
+    pub sort_key: SortKey,
+    pub order: Order,
+    pub limit: Option<usize>,
+}
+
+impl Default for Query {
+    fn default() -> Self {
+        Self {
+            filters: Vec::new(),
+            sort_key: SortKey::FinalScore,
+            order: Order::Desc,
+            limit: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Filter {
+    Media(MediaKind),
+    MinScore(f64),
+    MaxScore(f64),
+    MinFinalScore(f64),
+    MaxFinalScore(f64),
+    HasLink(bool),
+    HasQuestion(bool),
+    HasCtaShare(bool),
+    HasCtaReply(bool),
+    Oon(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaKind {
+    None,
+    Image,
+    Gif,
+    Video,
+    Live,
+    Carousel,
+}
+
+impl MediaKind {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "none" | "text" => Some(MediaKind::None),
+            "image" | "photo" | "pic" => Some(MediaKind::Image),
+            "gif" => Some(MediaKind::Gif),
+            "video" | "vid" => Some(MediaKind::Video),
+            "live" | "livestream" | "broadcast" => Some(MediaKind::Live),
+            "carousel" | "gallery" | "album" => Some(MediaKind::Carousel),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Score,
+    FinalScore,
+    WeightedScore,
+    ImpressionsIn,
+    ImpressionsOon,
+    ImpressionsTotal,
+    ExpectedUniqueEngagements,
+    ExpectedActionVolume,
+    UniqueEngagementRate,
+    ActionVolumeRate,
+    DiversityMultiplier,
+    OonMultiplier,
+}
+
+impl SortKey {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "score" => Some(SortKey::Score),
+            "final_score" => Some(SortKey::FinalScore),
+            "weighted_score" => Some(SortKey::WeightedScore),
+            "impressions_in" => Some(SortKey::ImpressionsIn),
+            "impressions_oon" => Some(SortKey::ImpressionsOon),
+            "impressions_total" => Some(SortKey::ImpressionsTotal),
+            "expected_unique_engagements" => Some(SortKey::ExpectedUniqueEngagements),
+            "expected_action_volume" => Some(SortKey::ExpectedActionVolume),
+            "unique_engagement_rate" => Some(SortKey::UniqueEngagementRate),
+            "action_volume_rate" => Some(SortKey::ActionVolumeRate),
+            "diversity_multiplier" => Some(SortKey::DiversityMultiplier),
+            "oon_multiplier" => Some(SortKey::OonMultiplier),
+            _ => None,
+        }
+    }
+
+    fn value(self, output: &SimulationOutput) -> f64 {
+        match self {
+            SortKey::Score => output.score,
+            SortKey::FinalScore => output.final_score,
+            SortKey::WeightedScore => output.weighted_score,
+            SortKey::ImpressionsIn => output.impressions_in,
+            SortKey::ImpressionsOon => output.impressions_oon,
+            SortKey::ImpressionsTotal => output.impressions_total,
+            SortKey::ExpectedUniqueEngagements => output.expected_unique_engagements,
+            SortKey::ExpectedActionVolume => output.expected_action_volume,
+            SortKey::UniqueEngagementRate => output.unique_engagement_rate,
+            SortKey::ActionVolumeRate => output.action_volume_rate,
+            SortKey::DiversityMultiplier => output.diversity_multiplier,
+            SortKey::OonMultiplier => output.oon_multiplier,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+impl Order {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "asc" => Some(Order::Asc),
+            "desc" => Some(Order::Desc),
+            _ => None,
+        }
+    }
+}
+
+struct Token {
+    text: String,
+    position: usize,
+}
+
+fn tokenize(source: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (i, ch) in source.char_indices() {
+        if ch.is_whitespace() {
+            if let Some(s) = start.take() {
+                tokens.push(Token {
+                    text: source[s..i].to_string(),
+                    position: s,
+                });
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(s) = start {
+        tokens.push(Token {
+            text: source[s..].to_string(),
+            position: s,
+        });
+    }
+
+    tokens
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        self.pos += 1;
+        token
+    }
+
+    fn parse(mut self) -> Result<Query, String> {
+        let mut query = Query::default();
+        while self.peek().is_some() {
+            self.parse_clause(&mut query)?;
+        }
+        Ok(query)
+    }
+
+    fn parse_clause(&mut self, query: &mut Query) -> Result<(), String> {
+        let (keyword, value, position) = {
+            let token = self.advance().expect("checked by caller");
+            let (keyword, value) = token.text.split_once(':').ok_or_else(|| {
+                parse_error(token.position, &format!("expected 'keyword:value', found '{}'", token.text))
+            })?;
+            (keyword.to_string(), value.to_string(), token.position)
+        };
+
+        match keyword.as_str() {
+            "sort" => {
+                query.sort_key = SortKey::from_str(&value)
+                    .ok_or_else(|| parse_error(position, &format!("unknown sort field '{}'", value)))?;
+                if let Some(next) = self.peek() {
+                    if let Some(order) = Order::from_str(&next.text) {
+                        query.order = order;
+                        self.advance();
+                    }
+                }
+            }
+            "limit" => {
+                query.limit = Some(parse_usize(&value, position)?);
+            }
+            "media" => {
+                let kind = MediaKind::from_str(&value)
+                    .ok_or_else(|| parse_error(position, &format!("unknown media kind '{}'", value)))?;
+                query.filters.push(Filter::Media(kind));
+            }
+            "min_score" => query.filters.push(Filter::MinScore(parse_f64(&value, position)?)),
+            "max_score" => query.filters.push(Filter::MaxScore(parse_f64(&value, position)?)),
+            "min_final_score" => {
+                query.filters.push(Filter::MinFinalScore(parse_f64(&value, position)?))
+            }
+            "max_final_score" => {
+                query.filters.push(Filter::MaxFinalScore(parse_f64(&value, position)?))
+            }
+            "has" => query.filters.push(parse_presence_filter(&value, position, true)?),
+            "exclude" => query.filters.push(parse_presence_filter(&value, position, false)?),
+            "oon" => query.filters.push(Filter::Oon(parse_bool(&value, position)?)),
+            other => return Err(parse_error(position, &format!("unknown keyword '{}'", other))),
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_presence_filter(value: &str, position: usize, present: bool) -> Result<Filter, String> {
+    match value {
+        "link" => Ok(Filter::HasLink(present)),
+        "question" => Ok(Filter::HasQuestion(present)),
+        "cta_share" => Ok(Filter::HasCtaShare(present)),
+        "cta_reply" => Ok(Filter::HasCtaReply(present)),
+        other => Err(parse_error(position, &format!("unknown predicate '{}'", other))),
+    }
+}
+
+fn parse_f64(value: &str, position: usize) -> Result<f64, String> {
+    value
+        .parse::<f64>()
+        .map_err(|_| parse_error(position, &format!("expected a number, found '{}'", value)))
+}
+
+fn parse_usize(value: &str, position: usize) -> Result<usize, String> {
+    value
+        .parse::<usize>()
+        .map_err(|_| parse_error(position, &format!("expected a non-negative integer, found '{}'", value)))
+}
+
+fn parse_bool(value: &str, position: usize) -> Result<bool, String> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        other => Err(parse_error(position, &format!("expected 'true' or 'false', found '{}'", other))),
+    }
+}
+
+fn parse_error(position: usize, message: &str) -> String {
+    format!("query parse error at position {}: {}", position, message)
+}
+
+fn media_kind(media: MediaType) -> MediaKind {
+    match media {
+        MediaType::None => MediaKind::None,
+        MediaType::Image => MediaKind::Image,
+        MediaType::Gif => MediaKind::Gif,
+        MediaType::Video { .. } => MediaKind::Video,
+        MediaType::Carousel { .. } => MediaKind::Carousel,
+    }
+}
+
+fn matches_filter(
+    filter: &Filter,
+    input: &SimulatorInput,
+    output: &SimulationOutput,
+    features: &TextFeatures,
+) -> bool {
+    match filter {
+        Filter::Media(MediaKind::Live) => input.media.is_live(),
+        Filter::Media(kind) => media_kind(input.media) == *kind,
+        Filter::MinScore(min) => output.score >= *min,
+        Filter::MaxScore(max) => output.score <= *max,
+        Filter::MinFinalScore(min) => output.final_score >= *min,
+        Filter::MaxFinalScore(max) => output.final_score <= *max,
+        Filter::HasLink(expected) => (features.urls > 0) == *expected,
+        Filter::HasQuestion(expected) => (features.questions > 0) == *expected,
+        Filter::HasCtaShare(expected) => features.cta_share == *expected,
+        Filter::HasCtaReply(expected) => features.cta_reply == *expected,
+        Filter::Oon(expected) => input.is_oon == *expected,
+    }
+}
+
+impl Query {
+    /// Parses a query string like
+    /// `sort:final_score desc media:video min_score:60 exclude:link has:question`.
+    /// Returns a parse error with a byte position so malformed queries are
+    /// rejected rather than silently ignored.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        Parser::new(tokenize(source)).parse()
+    }
+
+    /// Filters and sorts `candidates` (paired inputs/outputs, since several
+    /// filters read raw `SimulatorInput`/text-feature state that
+    /// `SimulationOutput` doesn't carry), truncating to `limit` if set.
+    pub fn apply(
+        &self,
+        candidates: &[(SimulatorInput, SimulationOutput)],
+        config: &ScoringConfig,
+    ) -> Vec<SimulationOutput> {
+        let mut matched: Vec<SimulationOutput> = candidates
+            .iter()
+            .filter(|(input, output)| {
+                let features = extract_text_features(&input.text, config);
+                self.filters
+                    .iter()
+                    .all(|filter| matches_filter(filter, input, output, &features))
+            })
+            .map(|(_, output)| output.clone())
+            .collect();
+
+        matched.sort_by(|a, b| {
+            let ordering = self
+                .sort_key
+                .value(a)
+                .partial_cmp(&self.sort_key.value(b))
+                .unwrap_or(std::cmp::Ordering::Equal);
+            match self.order {
+                Order::Asc => ordering,
+                Order::Desc => ordering.reverse(),
+            }
+        });
+
+        if let Some(limit) = self.limit {
+            matched.truncate(limit);
+        }
+
+        matched
+    }
+}
@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use std::path::PathBuf;
+use virality_sim::config::ScoringConfig;
+use virality_sim::scoring::ActionWeights;
+use virality_sim::user::{UserProfile, UserProfileStore};
+
+use crate::repository::Repository;
+use crate::snapshots::{RawByteStream, Snapshot, SnapshotQuery, SnapshotQueryResult, SnapshotStore};
+
+/// The original single-node backend: snapshots and user profiles as JSON
+/// files on disk (`SnapshotStore`/`UserProfileStore`), scoring weights
+/// persisted back into the TOML config at `config_path`.
+pub struct FileRepository {
+    snapshots: SnapshotStore,
+    profiles: UserProfileStore,
+    config_path: Option<PathBuf>,
+}
+
+impl FileRepository {
+    pub async fn open(
+        snapshot_path: PathBuf,
+        profiles_path: PathBuf,
+        config_path: Option<PathBuf>,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            snapshots: SnapshotStore::load(snapshot_path).await?,
+            profiles: UserProfileStore::load(profiles_path).await?,
+            config_path,
+        })
+    }
+
+    /// Like [`Self::open`], but with an explicit snapshot retention cap
+    /// (`None` disables truncation) instead of the default 50.
+    pub async fn open_with_retention(
+        snapshot_path: PathBuf,
+        profiles_path: PathBuf,
+        config_path: Option<PathBuf>,
+        snapshot_retention: Option<usize>,
+    ) -> Result<Self, String> {
+        Ok(Self {
+            snapshots: SnapshotStore::load_with_retention(snapshot_path, snapshot_retention).await?,
+            profiles: UserProfileStore::load(profiles_path).await?,
+            config_path,
+        })
+    }
+}
+
+#[async_trait]
+impl Repository for FileRepository {
+    async fn get_snapshot(&self, id: &str) -> Result<Option<Snapshot>, String> {
+        Ok(self.snapshots.get(id).await)
+    }
+
+    async fn list_snapshots(&self) -> Result<Vec<Snapshot>, String> {
+        Ok(self.snapshots.list().await)
+    }
+
+    async fn add_snapshot(&self, snapshot: Snapshot) -> Result<Snapshot, String> {
+        self.snapshots.add(snapshot).await
+    }
+
+    async fn add_snapshot_raw(
+        &self,
+        id: String,
+        created_at: String,
+        input: serde_json::Value,
+        output_stream: RawByteStream,
+    ) -> Result<Snapshot, String> {
+        self.snapshots
+            .add_streamed(id, created_at, input, output_stream)
+            .await
+    }
+
+    async fn query_snapshots(&self, query: SnapshotQuery) -> Result<SnapshotQueryResult, String> {
+        Ok(self.snapshots.query(query).await)
+    }
+
+    async fn delete_snapshot(&self, id: &str) -> Result<bool, String> {
+        self.snapshots.delete(id).await
+    }
+
+    async fn health_check(&self) -> Result<(), String> {
+        self.snapshots.health_check().await
+    }
+
+    async fn get_profile(&self, user_id: &str) -> Result<Option<UserProfile>, String> {
+        Ok(self.profiles.get(user_id).await)
+    }
+
+    async fn upsert_profile(&self, profile: UserProfile) -> Result<UserProfile, String> {
+        self.profiles.upsert(profile).await
+    }
+
+    async fn load_config(&self) -> Result<ScoringConfig, String> {
+        ScoringConfig::load(self.config_path.clone()).map(|(config, _, _)| config)
+    }
+
+    async fn save_weights(&self, weights: &ActionWeights) -> Result<(), String> {
+        let Some(path) = self.config_path.as_ref() else {
+            return Ok(());
+        };
+        let (mut config, _, _) = ScoringConfig::load(Some(path.clone()))?;
+        config.weights = weights.clone();
+        config.write(path)
+    }
+}
@@ -0,0 +1,56 @@
+pub mod file;
+pub mod postgres;
+
+use async_trait::async_trait;
+use virality_sim::config::ScoringConfig;
+use virality_sim::scoring::ActionWeights;
+use virality_sim::user::UserProfile;
+
+use crate::snapshots::{RawByteStream, Snapshot, SnapshotQuery, SnapshotQueryResult};
+
+pub use file::FileRepository;
+pub use postgres::PostgresRepository;
+
+/// Durable state the server depends on: snapshots, user profiles, and the
+/// scoring config's tunable weights. `serve()` selects one implementation
+/// behind `Arc<dyn Repository>` based on `ServeArgs`/`DATABASE_URL` --
+/// `FileRepository` (the original single-node JSON-file behavior) or
+/// `PostgresRepository` (pooled, shared across replicas) -- so the rest of
+/// the server only ever talks to the trait and doesn't care which backend
+/// is live.
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn get_snapshot(&self, id: &str) -> Result<Option<Snapshot>, String>;
+    async fn list_snapshots(&self) -> Result<Vec<Snapshot>, String>;
+    async fn add_snapshot(&self, snapshot: Snapshot) -> Result<Snapshot, String>;
+    /// Ingests a snapshot whose `output` arrives as a raw byte stream (e.g.
+    /// straight off an HTTP request body) instead of a pre-parsed `Snapshot`,
+    /// so large payloads don't have to be fully buffered by the caller first.
+    async fn add_snapshot_raw(
+        &self,
+        id: String,
+        created_at: String,
+        input: serde_json::Value,
+        output_stream: RawByteStream,
+    ) -> Result<Snapshot, String>;
+    async fn query_snapshots(&self, query: SnapshotQuery) -> Result<SnapshotQueryResult, String>;
+    async fn delete_snapshot(&self, id: &str) -> Result<bool, String>;
+
+    /// Verifies this backend's snapshot storage is reachable and writable,
+    /// surfacing a structured error rather than failing only on the next
+    /// `add_snapshot`. Backs the `/api/health` endpoint.
+    async fn health_check(&self) -> Result<(), String>;
+
+    async fn get_profile(&self, user_id: &str) -> Result<Option<UserProfile>, String>;
+    async fn upsert_profile(&self, profile: UserProfile) -> Result<UserProfile, String>;
+
+    /// Loads the scoring config this backend has persisted, if any. File
+    /// backend defers entirely to `ScoringConfig::load`'s own versioning and
+    /// migration machinery; Postgres starts new deployments from
+    /// `ScoringConfig::default()`.
+    async fn load_config(&self) -> Result<ScoringConfig, String>;
+    /// Persists `weights` as the backend's durable copy of the tunable
+    /// scoring weights, so `PUT /api/config/weights` survives a restart (and,
+    /// for Postgres, is immediately visible to every other replica).
+    async fn save_weights(&self, weights: &ActionWeights) -> Result<(), String>;
+}
@@ -0,0 +1,400 @@
+use async_trait::async_trait;
+use deadpool_postgres::{Config as PoolConfig, Pool, Runtime};
+use tokio_postgres::NoTls;
+use virality_sim::config::ScoringConfig;
+use virality_sim::ids::UserId;
+use virality_sim::scoring::ActionWeights;
+use virality_sim::user::{EngagementEvent, UserProfile};
+
+use crate::repository::Repository;
+use crate::snapshots::{RawByteStream, Snapshot, SnapshotQuery, SnapshotQueryResult};
+use tokio_stream::StreamExt;
+
+/// Shared backend for multi-replica deployments: snapshots and user
+/// profiles live in Postgres as JSONB blobs keyed by id, behind a
+/// `deadpool-postgres` connection pool. Schema is created lazily on
+/// `connect` so a fresh database just works.
+pub struct PostgresRepository {
+    pool: Pool,
+    /// Mirrors `FileRepository`'s snapshot retention cap; `None` disables
+    /// eviction. Applied after every insert in `add_snapshot`.
+    snapshot_retention: Option<usize>,
+}
+
+impl PostgresRepository {
+    pub async fn connect(database_url: &str, snapshot_retention: Option<usize>) -> Result<Self, String> {
+        let mut config = PoolConfig::new();
+        config.url = Some(database_url.to_string());
+        let pool = config
+            .create_pool(Some(Runtime::Tokio1), NoTls)
+            .map_err(|err| format!("failed to create postgres pool: {}", err))?;
+
+        let client = pool
+            .get()
+            .await
+            .map_err(|err| format!("failed to connect to postgres: {}", err))?;
+        client
+            .batch_execute(
+                "CREATE TABLE IF NOT EXISTS snapshots (
+                    id TEXT PRIMARY KEY,
+                    created_at TEXT NOT NULL,
+                    input JSONB NOT NULL,
+                    output JSONB NOT NULL
+                );
+                CREATE TABLE IF NOT EXISTS user_profiles (
+                    user_id TEXT PRIMARY KEY,
+                    followers BIGINT NOT NULL,
+                    following BIGINT NOT NULL,
+                    account_age_days INTEGER NOT NULL,
+                    verified BOOLEAN NOT NULL,
+                    engagement_history JSONB NOT NULL
+                );
+                CREATE TABLE IF NOT EXISTS scoring_weights (
+                    id INTEGER PRIMARY KEY CHECK (id = 1),
+                    weights JSONB NOT NULL
+                );",
+            )
+            .await
+            .map_err(|err| format!("failed to create schema: {}", err))?;
+
+        Ok(Self {
+            pool,
+            snapshot_retention,
+        })
+    }
+}
+
+#[async_trait]
+impl Repository for PostgresRepository {
+    async fn get_snapshot(&self, id: &str) -> Result<Option<Snapshot>, String> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| format!("failed to get postgres connection: {}", err))?;
+        let row = client
+            .query_opt(
+                "SELECT id, created_at, input, output FROM snapshots WHERE id = $1",
+                &[&id],
+            )
+            .await
+            .map_err(|err| format!("failed to query snapshot: {}", err))?;
+        Ok(row.map(|row| Snapshot {
+            id: row.get(0),
+            created_at: row.get(1),
+            input: row.get(2),
+            output: row.get(3),
+        }))
+    }
+
+    async fn list_snapshots(&self) -> Result<Vec<Snapshot>, String> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| format!("failed to get postgres connection: {}", err))?;
+        let rows = client
+            .query(
+                "SELECT id, created_at, input, output FROM snapshots ORDER BY created_at DESC LIMIT 50",
+                &[],
+            )
+            .await
+            .map_err(|err| format!("failed to list snapshots: {}", err))?;
+        Ok(rows
+            .into_iter()
+            .map(|row| Snapshot {
+                id: row.get(0),
+                created_at: row.get(1),
+                input: row.get(2),
+                output: row.get(3),
+            })
+            .collect())
+    }
+
+    async fn add_snapshot(&self, snapshot: Snapshot) -> Result<Snapshot, String> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| format!("failed to get postgres connection: {}", err))?;
+        client
+            .execute(
+                "INSERT INTO snapshots (id, created_at, input, output) VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (id) DO UPDATE SET created_at = EXCLUDED.created_at,
+                     input = EXCLUDED.input, output = EXCLUDED.output",
+                &[
+                    &snapshot.id,
+                    &snapshot.created_at,
+                    &snapshot.input,
+                    &snapshot.output,
+                ],
+            )
+            .await
+            .map_err(|err| format!("failed to insert snapshot: {}", err))?;
+
+        if let Some(cap) = self.snapshot_retention {
+            client
+                .execute(
+                    "DELETE FROM snapshots WHERE id NOT IN (
+                        SELECT id FROM snapshots ORDER BY created_at DESC LIMIT $1
+                    )",
+                    &[&(cap as i64)],
+                )
+                .await
+                .map_err(|err| format!("failed to evict old snapshots: {}", err))?;
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Postgres has no streaming JSONB bind path, so unlike
+    /// `FileRepository` this still has to drain `output_stream` into memory
+    /// before the insert -- the server-side bounded-memory benefit only
+    /// applies to the file backend.
+    async fn add_snapshot_raw(
+        &self,
+        id: String,
+        created_at: String,
+        input: serde_json::Value,
+        mut output_stream: RawByteStream,
+    ) -> Result<Snapshot, String> {
+        let mut output_bytes = Vec::new();
+        while let Some(chunk) = output_stream.next().await {
+            let chunk = chunk.map_err(|err| format!("failed to read snapshot output: {}", err))?;
+            output_bytes.extend_from_slice(&chunk);
+        }
+        let output: serde_json::Value = serde_json::from_slice(&output_bytes)
+            .map_err(|err| format!("invalid output JSON: {}", err))?;
+        self.add_snapshot(Snapshot {
+            id,
+            created_at,
+            input,
+            output,
+        })
+        .await
+    }
+
+    async fn query_snapshots(&self, query: SnapshotQuery) -> Result<SnapshotQueryResult, String> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| format!("failed to get postgres connection: {}", err))?;
+
+        let mut clauses = Vec::new();
+        let mut params: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> = Vec::new();
+        if let Some(after) = query.created_after.as_ref() {
+            params.push(after);
+            clauses.push(format!("created_at >= ${}", params.len()));
+        }
+        if let Some(before) = query.created_before.as_ref() {
+            params.push(before);
+            clauses.push(format!("created_at <= ${}", params.len()));
+        }
+        let like_pattern = query
+            .contains
+            .as_ref()
+            .map(|needle| format!("%{}%", escape_like_pattern(needle)));
+        if let Some(pattern) = like_pattern.as_ref() {
+            params.push(pattern);
+            let pattern_index = params.len();
+            match query.field.as_ref() {
+                Some(field) => {
+                    params.push(field);
+                    let field_index = params.len();
+                    clauses.push(format!(
+                        "((input -> ${field_index})::text LIKE ${pattern_index} ESCAPE '\\' \
+                         OR (output -> ${field_index})::text LIKE ${pattern_index} ESCAPE '\\')",
+                        field_index = field_index,
+                        pattern_index = pattern_index
+                    ));
+                }
+                None => {
+                    clauses.push(format!(
+                        "(input::text LIKE ${pattern_index} ESCAPE '\\' \
+                         OR output::text LIKE ${pattern_index} ESCAPE '\\')",
+                        pattern_index = pattern_index
+                    ));
+                }
+            }
+        }
+        let where_clause = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+
+        let count_sql = format!("SELECT COUNT(*) FROM snapshots {}", where_clause);
+        let total: i64 = client
+            .query_one(&count_sql, &params)
+            .await
+            .map_err(|err| format!("failed to count snapshots: {}", err))?
+            .get(0);
+
+        let limit = if query.limit == 0 { total.max(0) as i64 } else { query.limit as i64 };
+        let mut page_params = params.clone();
+        page_params.push(&limit);
+        let limit_index = page_params.len();
+        let offset = query.offset as i64;
+        page_params.push(&offset);
+        let offset_index = page_params.len();
+
+        let select_sql = format!(
+            "SELECT id, created_at, input, output FROM snapshots {} \
+             ORDER BY created_at DESC LIMIT ${} OFFSET ${}",
+            where_clause, limit_index, offset_index
+        );
+        let rows = client
+            .query(&select_sql, &page_params)
+            .await
+            .map_err(|err| format!("failed to query snapshots: {}", err))?;
+
+        Ok(SnapshotQueryResult {
+            results: rows
+                .into_iter()
+                .map(|row| Snapshot {
+                    id: row.get(0),
+                    created_at: row.get(1),
+                    input: row.get(2),
+                    output: row.get(3),
+                })
+                .collect(),
+            total: total.max(0) as usize,
+        })
+    }
+
+    async fn delete_snapshot(&self, id: &str) -> Result<bool, String> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| format!("failed to get postgres connection: {}", err))?;
+        let rows = client
+            .execute("DELETE FROM snapshots WHERE id = $1", &[&id])
+            .await
+            .map_err(|err| format!("failed to delete snapshot: {}", err))?;
+        Ok(rows > 0)
+    }
+
+    async fn health_check(&self) -> Result<(), String> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| format!("snapshot store unhealthy: failed to get postgres connection: {}", err))?;
+        client
+            .query_one("SELECT 1", &[])
+            .await
+            .map_err(|err| format!("snapshot store unhealthy: {}", err))?;
+        Ok(())
+    }
+
+    async fn get_profile(&self, user_id: &str) -> Result<Option<UserProfile>, String> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| format!("failed to get postgres connection: {}", err))?;
+        let row = client
+            .query_opt(
+                "SELECT user_id, followers, following, account_age_days, verified, engagement_history
+                 FROM user_profiles WHERE user_id = $1",
+                &[&user_id],
+            )
+            .await
+            .map_err(|err| format!("failed to query user profile: {}", err))?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let user_id: String = row.get(0);
+        let history: serde_json::Value = row.get(5);
+        let engagement_history: Vec<EngagementEvent> = serde_json::from_value(history)
+            .map_err(|err| format!("failed to decode engagement history: {}", err))?;
+        Ok(Some(UserProfile {
+            user_id: UserId::new(user_id)?,
+            followers: row.get::<_, i64>(1) as u64,
+            following: row.get::<_, i64>(2) as u64,
+            account_age_days: row.get::<_, i32>(3) as u32,
+            verified: row.get(4),
+            engagement_history,
+        }))
+    }
+
+    async fn upsert_profile(&self, profile: UserProfile) -> Result<UserProfile, String> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| format!("failed to get postgres connection: {}", err))?;
+        let history = serde_json::to_value(&profile.engagement_history)
+            .map_err(|err| format!("failed to encode engagement history: {}", err))?;
+        client
+            .execute(
+                "INSERT INTO user_profiles
+                    (user_id, followers, following, account_age_days, verified, engagement_history)
+                 VALUES ($1, $2, $3, $4, $5, $6)
+                 ON CONFLICT (user_id) DO UPDATE SET
+                    followers = EXCLUDED.followers,
+                    following = EXCLUDED.following,
+                    account_age_days = EXCLUDED.account_age_days,
+                    verified = EXCLUDED.verified,
+                    engagement_history = EXCLUDED.engagement_history",
+                &[
+                    &profile.user_id.to_string(),
+                    &(profile.followers as i64),
+                    &(profile.following as i64),
+                    &(profile.account_age_days as i32),
+                    &profile.verified,
+                    &history,
+                ],
+            )
+            .await
+            .map_err(|err| format!("failed to upsert user profile: {}", err))?;
+        Ok(profile)
+    }
+
+    async fn load_config(&self) -> Result<ScoringConfig, String> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| format!("failed to get postgres connection: {}", err))?;
+        let row = client
+            .query_opt("SELECT weights FROM scoring_weights WHERE id = 1", &[])
+            .await
+            .map_err(|err| format!("failed to query scoring weights: {}", err))?;
+        let mut config = ScoringConfig::default();
+        if let Some(row) = row {
+            let weights: serde_json::Value = row.get(0);
+            config.weights = serde_json::from_value(weights)
+                .map_err(|err| format!("failed to decode scoring weights: {}", err))?;
+        }
+        Ok(config)
+    }
+
+    async fn save_weights(&self, weights: &ActionWeights) -> Result<(), String> {
+        let client = self
+            .pool
+            .get()
+            .await
+            .map_err(|err| format!("failed to get postgres connection: {}", err))?;
+        let payload = serde_json::to_value(weights)
+            .map_err(|err| format!("failed to encode scoring weights: {}", err))?;
+        client
+            .execute(
+                "INSERT INTO scoring_weights (id, weights) VALUES (1, $1)
+                 ON CONFLICT (id) DO UPDATE SET weights = EXCLUDED.weights",
+                &[&payload],
+            )
+            .await
+            .map_err(|err| format!("failed to save scoring weights: {}", err))?;
+        Ok(())
+    }
+}
+
+/// Escapes `%`, `_`, and the escape character itself so a `contains` filter
+/// is matched as a literal substring (matching `SnapshotStore`'s
+/// `matches_contains`) instead of a SQL `LIKE` wildcard pattern.
+fn escape_like_pattern(needle: &str) -> String {
+    needle.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
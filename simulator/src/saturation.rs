@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+
+use rand::seq::SliceRandom;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{clamp01, normalize_text};
+
+/// Tunables for density-based `topic_saturation` scoring.
+#[derive(Debug, Clone)]
+pub struct SaturationConfig {
+    /// Token-Jaccard similarity at or above which two posts count as "the
+    /// same topic" for density purposes.
+    pub similarity_threshold: f64,
+    pub seed: u64,
+}
+
+impl Default for SaturationConfig {
+    fn default() -> Self {
+        Self {
+            similarity_threshold: 0.35,
+            seed: 0,
+        }
+    }
+}
+
+/// Per-candidate output of `score_batch_saturation`.
+#[derive(Debug, Clone)]
+pub struct SaturationResult {
+    /// Replaces the manual `SimulatorInput::topic_saturation` field: the
+    /// fraction of the batch (candidates plus `recent_posts` context) that
+    /// reads as the same topic as this post.
+    pub topic_saturation: f64,
+    pub nearby_count: usize,
+    /// Position this candidate landed at after the fairness shuffle. Use
+    /// this, not input order, to break ties when later deciding which of
+    /// several near-identical drafts to suppress.
+    pub dedup_rank: usize,
+}
+
+/// Scores `topic_saturation` for every post in `candidates` from how densely
+/// packed the batch is around it, rather than trusting a caller-supplied
+/// estimate. `recent_posts` is optional extra context (e.g. the author's
+/// last N posts) that contributes to the density count but is not itself
+/// scored.
+///
+/// Candidates and context are pooled and shuffled with a seeded RNG before
+/// the O(n^2) similarity pass runs, so that when several posts are
+/// near-duplicates, which one "started it" and which ones look redundant
+/// is decided fairly (via `dedup_rank`) rather than by whichever happened
+/// to come first in `candidates`.
+pub fn score_batch_saturation(
+    candidates: &[String],
+    recent_posts: &[String],
+    config: &SaturationConfig,
+) -> Vec<SaturationResult> {
+    if candidates.is_empty() {
+        return Vec::new();
+    }
+
+    let mut rng = StdRng::seed_from_u64(config.seed);
+
+    let mut pool: Vec<(Option<usize>, HashSet<String>)> = candidates
+        .iter()
+        .enumerate()
+        .map(|(index, text)| (Some(index), tokenize(text)))
+        .chain(recent_posts.iter().map(|text| (None, tokenize(text))))
+        .collect();
+    pool.shuffle(&mut rng);
+
+    let mut dedup_rank = vec![0usize; candidates.len()];
+    for (rank, (candidate_index, _)) in pool.iter().enumerate() {
+        if let Some(index) = candidate_index {
+            dedup_rank[*index] = rank;
+        }
+    }
+
+    let mut nearby_count = vec![0usize; candidates.len()];
+    for (i, (candidate_index, tokens)) in pool.iter().enumerate() {
+        let Some(index) = candidate_index else {
+            continue;
+        };
+        let mut count = 0usize;
+        for (j, (_, other_tokens)) in pool.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            if jaccard(tokens, other_tokens) >= config.similarity_threshold {
+                count += 1;
+            }
+        }
+        nearby_count[*index] = count;
+    }
+
+    let max_possible = (pool.len() - 1).max(1) as f64;
+    candidates
+        .iter()
+        .enumerate()
+        .map(|(index, _)| SaturationResult {
+            topic_saturation: clamp01(nearby_count[index] as f64 / max_possible),
+            nearby_count: nearby_count[index],
+            dedup_rank: dedup_rank[index],
+        })
+        .collect()
+}
+
+fn tokenize(text: &str) -> HashSet<String> {
+    normalize_text(text)
+        .split_whitespace()
+        .map(|token| token.to_string())
+        .collect()
+}
+
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let intersection = a.intersection(b).count() as f64;
+    let union = a.union(b).count() as f64;
+    if union == 0.0 {
+        0.0
+    } else {
+        intersection / union
+    }
+}
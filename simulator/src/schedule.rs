@@ -0,0 +1,222 @@
+use std::time::{Duration, Instant};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::config::ScoringConfig;
+use crate::{simulate_with_mode, ScoringMode, SimulatorInput};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    pub const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Weekday::Monday => "Monday",
+            Weekday::Tuesday => "Tuesday",
+            Weekday::Wednesday => "Wednesday",
+            Weekday::Thursday => "Thursday",
+            Weekday::Friday => "Friday",
+            Weekday::Saturday => "Saturday",
+            Weekday::Sunday => "Sunday",
+        }
+    }
+
+    fn index(self) -> i8 {
+        self as i8
+    }
+
+    fn from_index(index: i8) -> Self {
+        Weekday::ALL[index.rem_euclid(7) as usize]
+    }
+
+    fn shift(self, delta: i8) -> Self {
+        Weekday::from_index(self.index() + delta)
+    }
+
+    /// The core model has no day-of-week signal at all, so this is a light,
+    /// explicit assumption rather than something fit from data: weekend
+    /// browsing skews more casual, which this crate's `time_of_day_score`
+    /// curve (built for a weekday routine) tends to overstate slightly.
+    fn engagement_multiplier(self) -> f64 {
+        match self {
+            Weekday::Saturday | Weekday::Sunday => 0.92,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Share of the audience in a given UTC offset, used to weight the
+/// `time_of_day_score` mixture across the audience's local hours.
+#[derive(Debug, Clone, Copy)]
+pub struct TimezoneWeight {
+    pub utc_offset_hours: i8,
+    pub weight: f64,
+}
+
+/// Tunables for the simulated-annealing schedule search.
+#[derive(Debug, Clone)]
+pub struct ScheduleConfig {
+    pub iterations: usize,
+    pub initial_temperature: f64,
+    pub cooling_rate: f64,
+    pub time_budget: Duration,
+    pub top_k: usize,
+    pub seed: u64,
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 500,
+            initial_temperature: 8.0,
+            cooling_rate: 0.97,
+            time_budget: Duration::from_millis(200),
+            top_k: 3,
+            seed: 42,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduleSlot {
+    pub hour: u8,
+    pub weekday: Weekday,
+    pub score: f64,
+}
+
+/// Searches the 24x7 `(hour, weekday)` grid via simulated annealing for the
+/// posting slot that maximizes the predicted virality score, given an
+/// audience timezone distribution. Replaces the static "post 9-11am or
+/// 7-9pm" suggestion with concrete, per-audience guidance.
+///
+/// The objective at each slot re-runs the full heuristic pipeline once per
+/// `timezones` entry with `hour_of_day` shifted to that segment's local
+/// hour (so `time_of_day_score`, and everything downstream of it, is
+/// genuinely recomputed for the slot) and weight-averages the resulting
+/// scores; `Weekday::engagement_multiplier` then applies a light day-of-week
+/// adjustment the core model otherwise has no concept of.
+///
+/// Neighbors perturb the hour by +/-1 or shift the weekday by a day; worse
+/// states are accepted with probability `exp(-delta / temperature)`, and
+/// `temperature` cools geometrically by `cooling_rate` each iteration.
+/// Search stops at `config.iterations` or `config.time_budget`, whichever
+/// comes first. Returns up to `config.top_k` distinct slots visited, best
+/// score first.
+pub fn optimize_schedule(
+    input: &SimulatorInput,
+    timezones: &[TimezoneWeight],
+    scoring_config: &ScoringConfig,
+    config: &ScheduleConfig,
+) -> Vec<ScheduleSlot> {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+
+    let mut current = (rng.gen_range(0..24u8), Weekday::from_index(rng.gen_range(0..7)));
+    let mut current_score = objective(input, current.0, current.1, timezones, scoring_config);
+
+    let mut top_slots = vec![ScheduleSlot {
+        hour: current.0,
+        weekday: current.1,
+        score: current_score,
+    }];
+
+    let mut temperature = config.initial_temperature;
+    let deadline = Instant::now() + config.time_budget;
+    let mut iteration = 0usize;
+
+    while iteration < config.iterations && Instant::now() < deadline {
+        let candidate = perturb(&mut rng, current);
+        let candidate_score = objective(input, candidate.0, candidate.1, timezones, scoring_config);
+
+        let delta = candidate_score - current_score;
+        let accept = delta >= 0.0 || rng.gen::<f64>() < (delta / temperature.max(1e-6)).exp();
+        if accept {
+            current = candidate;
+            current_score = candidate_score;
+            record_top_slot(
+                &mut top_slots,
+                ScheduleSlot {
+                    hour: candidate.0,
+                    weekday: candidate.1,
+                    score: candidate_score,
+                },
+                config.top_k,
+            );
+        }
+
+        temperature *= config.cooling_rate;
+        iteration += 1;
+    }
+
+    top_slots
+}
+
+fn perturb(rng: &mut StdRng, (hour, weekday): (u8, Weekday)) -> (u8, Weekday) {
+    if rng.gen_bool(0.5) {
+        let delta: i16 = if rng.gen_bool(0.5) { 1 } else { -1 };
+        let next_hour = ((hour as i16 + delta).rem_euclid(24)) as u8;
+        (next_hour, weekday)
+    } else {
+        let delta: i8 = if rng.gen_bool(0.5) { 1 } else { -1 };
+        (hour, weekday.shift(delta))
+    }
+}
+
+fn record_top_slot(top_slots: &mut Vec<ScheduleSlot>, candidate: ScheduleSlot, top_k: usize) {
+    if top_slots
+        .iter()
+        .any(|slot| slot.hour == candidate.hour && slot.weekday == candidate.weekday)
+    {
+        return;
+    }
+    top_slots.push(candidate);
+    top_slots.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    top_slots.truncate(top_k.max(1));
+}
+
+fn objective(
+    input: &SimulatorInput,
+    hour: u8,
+    weekday: Weekday,
+    timezones: &[TimezoneWeight],
+    scoring_config: &ScoringConfig,
+) -> f64 {
+    let weight_sum: f64 = timezones.iter().map(|tz| tz.weight.max(0.0)).sum();
+    let mixed_score = if timezones.is_empty() || weight_sum <= 0.0 {
+        score_at_hour(input, hour, scoring_config)
+    } else {
+        timezones
+            .iter()
+            .map(|tz| {
+                let local_hour = (hour as i16 + tz.utc_offset_hours as i16).rem_euclid(24) as u8;
+                tz.weight.max(0.0) * score_at_hour(input, local_hour, scoring_config)
+            })
+            .sum::<f64>()
+            / weight_sum
+    };
+
+    mixed_score * weekday.engagement_multiplier()
+}
+
+fn score_at_hour(input: &SimulatorInput, hour: u8, scoring_config: &ScoringConfig) -> f64 {
+    let mut variant = input.clone();
+    variant.hour_of_day = hour;
+    simulate_with_mode(&variant, None, None, ScoringMode::Heuristic, None, scoring_config).score
+}
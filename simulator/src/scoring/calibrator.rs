@@ -0,0 +1,212 @@
+use crate::scoring::ActionWeights;
+use crate::ActionProbs;
+
+const POSITIVE_SIGNALS: usize = 13;
+const NEGATIVE_SIGNALS: usize = 5;
+const SIGNAL_COUNT: usize = POSITIVE_SIGNALS + NEGATIVE_SIGNALS;
+
+/// A single post's observed action rates paired with a downstream outcome
+/// (e.g. total engagements accrued by the end of an observation window).
+/// Typically produced from `XStreamClient` snapshots rather than constructed
+/// directly.
+#[derive(Debug, Clone)]
+pub struct ObservedOutcome {
+    pub actions: ActionProbs,
+    pub outcome: f64,
+}
+
+/// The result of a `WeightCalibrator::fit`, including whether enough
+/// samples were seen to trust it.
+#[derive(Debug, Clone)]
+pub struct FitResult {
+    pub weights: ActionWeights,
+    pub sample_count: usize,
+    pub reliable: bool,
+}
+
+/// Accumulates `ObservedOutcome`s and fits linear `ActionWeights` against
+/// them, so the weights `WeightedScorer::score` uses can be grounded in real
+/// engagement instead of hand-tuned defaults.
+#[derive(Debug, Clone, Default)]
+pub struct WeightCalibrator {
+    observations: Vec<ObservedOutcome>,
+}
+
+impl WeightCalibrator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, actions: ActionProbs, outcome: f64) {
+        self.observations.push(ObservedOutcome { actions, outcome });
+    }
+
+    pub fn sample_count(&self) -> usize {
+        self.observations.len()
+    }
+
+    /// Fits `ActionWeights` via ridge-regularized least squares: builds the
+    /// design matrix `X` (rows = posts, columns = the 13 positive action
+    /// signals plus the 5 negative ones), the target vector `y` (the
+    /// outcome), and solves `(XᵀX + λI)w = Xᵀy` via Cholesky decomposition.
+    /// The negative-action weights (`not_interested`, `block`, `mute`,
+    /// `report`, `hide_post`) are projected back to non-positive afterward,
+    /// since nothing in the unconstrained solve otherwise respects that
+    /// sign. Warns (and marks the result unreliable) when fewer than `min_samples`
+    /// observations were seen.
+    pub fn fit(&self, lambda: f64, min_samples: usize) -> FitResult {
+        let sample_count = self.observations.len();
+        let reliable = sample_count >= min_samples;
+        if !reliable {
+            tracing::warn!(
+                sample_count,
+                min_samples,
+                "WeightCalibrator fit from too few samples to be reliable"
+            );
+        }
+
+        if self.observations.is_empty() {
+            return FitResult {
+                weights: ActionWeights::default(),
+                sample_count,
+                reliable,
+            };
+        }
+
+        let design: Vec<[f64; SIGNAL_COUNT]> = self
+            .observations
+            .iter()
+            .map(|obs| signal_row(&obs.actions))
+            .collect();
+        let targets: Vec<f64> = self.observations.iter().map(|obs| obs.outcome).collect();
+
+        let gram = gram_matrix(&design, lambda);
+        let projection = weighted_sum(&design, &targets);
+        let solved = solve_cholesky(&gram, &projection).unwrap_or_else(|| vec![0.0; SIGNAL_COUNT]);
+
+        FitResult {
+            weights: weights_from_signals(&solved),
+            sample_count,
+            reliable,
+        }
+    }
+}
+
+fn signal_row(actions: &ActionProbs) -> [f64; SIGNAL_COUNT] {
+    [
+        actions.like,
+        actions.reply,
+        actions.repost,
+        actions.photo_expand,
+        actions.click,
+        actions.profile_click,
+        actions.share,
+        actions.share_dm,
+        actions.share_link,
+        actions.dwell,
+        actions.quote,
+        actions.quoted_click,
+        actions.follow_author,
+        actions.not_interested,
+        actions.block,
+        actions.mute,
+        actions.report,
+        actions.hide_post,
+    ]
+}
+
+fn weights_from_signals(values: &[f64]) -> ActionWeights {
+    let mut weights = ActionWeights::default();
+    weights.favorite = values[0];
+    weights.reply = values[1];
+    weights.repost = values[2];
+    weights.photo_expand = values[3];
+    weights.click = values[4];
+    weights.profile_click = values[5];
+    weights.share = values[6];
+    weights.share_dm = values[7];
+    weights.share_link = values[8];
+    weights.dwell = values[9];
+    weights.quote = values[10];
+    weights.quoted_click = values[11];
+    weights.follow_author = values[12];
+    weights.not_interested = values[13].min(0.0);
+    weights.block = values[14].min(0.0);
+    weights.mute = values[15].min(0.0);
+    weights.report = values[16].min(0.0);
+    weights.hide_post = values[17].min(0.0);
+    weights
+}
+
+/// Builds `XᵀX + λI`.
+fn gram_matrix(design: &[[f64; SIGNAL_COUNT]], lambda: f64) -> Vec<Vec<f64>> {
+    let mut gram = vec![vec![0.0; SIGNAL_COUNT]; SIGNAL_COUNT];
+    for row in design {
+        for i in 0..SIGNAL_COUNT {
+            for j in 0..SIGNAL_COUNT {
+                gram[i][j] += row[i] * row[j];
+            }
+        }
+    }
+    for (i, diagonal) in gram.iter_mut().enumerate() {
+        diagonal[i] += lambda;
+    }
+    gram
+}
+
+/// Builds `Xᵀy`.
+fn weighted_sum(design: &[[f64; SIGNAL_COUNT]], targets: &[f64]) -> Vec<f64> {
+    let mut out = vec![0.0; SIGNAL_COUNT];
+    for (row, target) in design.iter().zip(targets) {
+        for i in 0..SIGNAL_COUNT {
+            out[i] += row[i] * target;
+        }
+    }
+    out
+}
+
+/// Solves the symmetric positive-definite system `a * x = b` via Cholesky
+/// decomposition (`a = L * Lᵀ`). Returns `None` if `a` isn't PD, which
+/// shouldn't happen once `λI` has been added unless `λ` is zero and the
+/// design is degenerate.
+fn solve_cholesky(a: &[Vec<f64>], b: &[f64]) -> Option<Vec<f64>> {
+    let n = b.len();
+    let mut l = vec![vec![0.0; n]; n];
+
+    for i in 0..n {
+        for j in 0..=i {
+            let mut sum = a[i][j];
+            for k in 0..j {
+                sum -= l[i][k] * l[j][k];
+            }
+            if i == j {
+                if sum <= 0.0 {
+                    return None;
+                }
+                l[i][j] = sum.sqrt();
+            } else {
+                l[i][j] = sum / l[j][j];
+            }
+        }
+    }
+
+    let mut y = vec![0.0; n];
+    for i in 0..n {
+        let mut sum = b[i];
+        for (k, y_k) in y.iter().enumerate().take(i) {
+            sum -= l[i][k] * y_k;
+        }
+        y[i] = sum / l[i][i];
+    }
+
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let mut sum = y[i];
+        for k in (i + 1)..n {
+            sum -= l[k][i] * x[k];
+        }
+        x[i] = sum / l[i][i];
+    }
+
+    Some(x)
+}
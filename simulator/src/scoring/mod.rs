@@ -1,9 +1,13 @@
+pub mod calibrator;
 pub mod diversity;
+pub mod moderation;
 pub mod oon;
 pub mod pipeline;
 pub mod weighted;
 
+pub use calibrator::{FitResult, ObservedOutcome, WeightCalibrator};
 pub use diversity::{AuthorDiversityConfig, AuthorDiversityScorer};
+pub use moderation::{ModerationConfig, ModerationResult, ModerationScorer};
 pub use oon::{OonScorer, OonScorerConfig};
 pub use pipeline::{ScoredCandidate, ScoringPipeline};
 pub use weighted::{ActionWeights, WeightedScorer};
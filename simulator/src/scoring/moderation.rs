@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModerationConfig {
+    pub profanity: Vec<String>,
+    pub slurs: Vec<String>,
+    pub threats: Vec<String>,
+    pub spam_patterns: Vec<String>,
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            profanity: vec![
+                "damn".to_string(),
+                "hell".to_string(),
+                "crap".to_string(),
+            ],
+            slurs: Vec::new(),
+            threats: vec![
+                "kill you".to_string(),
+                "i will hurt".to_string(),
+                "going to hurt".to_string(),
+            ],
+            spam_patterns: vec![
+                "click here".to_string(),
+                "free money".to_string(),
+                "act now".to_string(),
+                "limited time offer".to_string(),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ModerationResult {
+    pub toxicity: f64,
+    pub categories: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModerationScorer {
+    config: ModerationConfig,
+}
+
+impl ModerationScorer {
+    pub fn new(config: ModerationConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn analyze(&self, text: &str) -> ModerationResult {
+        let lowercase = text.to_lowercase();
+        let mut categories = Vec::new();
+        let mut toxicity = 0.0;
+
+        let profanity_hits = count_hits(&lowercase, &self.config.profanity);
+        if profanity_hits > 0 {
+            categories.push("profanity".to_string());
+            toxicity += 0.2 * (profanity_hits as f64 / 3.0).min(1.0);
+        }
+
+        let slur_hits = count_hits(&lowercase, &self.config.slurs);
+        if slur_hits > 0 {
+            categories.push("slur".to_string());
+            toxicity += 0.6;
+        }
+
+        let threat_hits = count_hits(&lowercase, &self.config.threats);
+        if threat_hits > 0 {
+            categories.push("threat".to_string());
+            toxicity += 0.5;
+        }
+
+        let spam_hits = count_hits(&lowercase, &self.config.spam_patterns);
+        if spam_hits > 0 {
+            categories.push("spam".to_string());
+            toxicity += 0.15 * (spam_hits as f64 / 2.0).min(1.0);
+        }
+
+        ModerationResult {
+            toxicity: toxicity.max(0.0).min(1.0),
+            categories,
+        }
+    }
+}
+
+fn count_hits(haystack: &str, needles: &[String]) -> usize {
+    needles
+        .iter()
+        .filter(|needle| !needle.is_empty() && haystack.contains(needle.as_str()))
+        .count()
+}
@@ -14,6 +14,11 @@ pub struct ScoredCandidate {
     pub diversity_multiplier: f64,
     pub oon_multiplier: f64,
     pub score: f64,
+    /// Author/post-derived fields, filled in bulk by `hydrate_candidates`
+    /// instead of one fetch per candidate. Zero/`None` until hydrated.
+    pub author_followers: u64,
+    pub author_verified: Option<bool>,
+    pub post_impressions: Option<u64>,
 }
 
 impl ScoredCandidate {
@@ -34,6 +39,9 @@ impl ScoredCandidate {
             diversity_multiplier: 1.0,
             oon_multiplier: 1.0,
             score: 0.0,
+            author_followers: 0,
+            author_verified: None,
+            post_impressions: None,
         }
     }
 }
@@ -22,6 +22,7 @@ pub struct ActionWeights {
     pub block: f64,
     pub mute: f64,
     pub report: f64,
+    pub hide_post: f64,
     pub dwell_time: f64,
 }
 
@@ -46,6 +47,7 @@ impl Default for ActionWeights {
             block: -5.0,
             mute: -3.0,
             report: -6.0,
+            hide_post: -3.5,
             dwell_time: 0.1,
         }
     }
@@ -94,6 +96,7 @@ impl WeightedScorer {
         score += actions.block * self.weights.block;
         score += actions.mute * self.weights.mute;
         score += actions.report * self.weights.report;
+        score += actions.hide_post * self.weights.hide_post;
 
         score += actions.dwell_time * self.weights.dwell_time;
 
@@ -1,6 +1,6 @@
 use axum::{
     extract::{Query, State},
-    http::StatusCode,
+    http::{HeaderMap, HeaderValue, StatusCode},
     response::{
         sse::{Event, KeepAlive, Sse},
         IntoResponse,
@@ -10,45 +10,62 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     net::SocketAddr,
     path::PathBuf,
     sync::{
-        atomic::{AtomicBool, AtomicUsize, Ordering},
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
     time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::sync::{broadcast, Mutex, RwLock};
-use tokio_stream::{wrappers::BroadcastStream, StreamExt};
+use tokio_stream::{
+    wrappers::{BroadcastStream, ReceiverStream},
+    StreamExt,
+};
 use tower_http::services::{ServeDir, ServeFile};
 
 use crate::api::{ApiSimulationRequest, ApiSimulationResponse};
-use crate::llm::{prompt_for_text, LlmClient};
-use crate::snapshots::{Snapshot, SnapshotStore};
+use crate::llm::{self, ScoringBackend};
+use crate::openai_compat::{
+    self, ChatCompletionChoice, ChatCompletionChunk, ChatCompletionChunkChoice,
+    ChatCompletionDelta, ChatCompletionRequest, ChatCompletionResponse, ChatMessage,
+    CompletionChoice, CompletionRequest, CompletionResponse, OpenAiError,
+};
+use crate::platform::{MastodonClient, PlatformProfile, SocialPlatform};
+use crate::repository::{FileRepository, PostgresRepository, Repository};
+use crate::snapshots::{RawByteStream, Snapshot, SnapshotQuery, SnapshotQueryResult};
+use crate::token_store::{TokenStore, XUserToken};
 use crate::x_api::{XApiClient, XUserProfile};
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use base64::Engine;
 use rand::RngCore;
 use sha2::{Digest, Sha256};
+use virality_sim::calibration::{ObservedOutcome, TraceContext, TraceStore};
 use virality_sim::config::ScoringConfig;
+use virality_sim::ids::{AuthorId, PostId, UserId};
 use virality_sim::phoenix_client::{PhoenixClient, PostFeatures, RankingRequest};
 use virality_sim::scoring::ActionWeights;
 use virality_sim::user::{
     action_flags_to_vector, generate_synthetic_history, profile_from_input, EngagementEvent,
-    UserProfile, UserProfileStore,
+    UserProfile,
 };
 use virality_sim::{simulate_with_mode, ActionProbs, MediaType, ScoringMode, SimulatorInput};
 
 #[derive(Clone)]
 struct AppState {
-    llm_client: Option<LlmClient>,
+    llm_client: Option<Arc<dyn ScoringBackend>>,
+    embeddings_client: Option<Arc<crate::embeddings::EmbeddingsClient>>,
+    trace_store: Option<Arc<TraceStore>>,
     x_client: Option<XApiClient>,
-    x_user_token: Arc<Mutex<Option<XUserToken>>>,
-    oauth_state: Arc<Mutex<HashMap<String, String>>>,
-    channels: Arc<Mutex<HashMap<String, broadcast::Sender<StreamEvent>>>>,
-    snapshots: Arc<SnapshotStore>,
-    user_profiles: Arc<UserProfileStore>,
+    mastodon_client: Option<MastodonClient>,
+    token_store: Arc<TokenStore>,
+    oauth_state: Arc<Mutex<HashMap<String, PendingOAuthState>>>,
+    device_codes: Arc<Mutex<HashMap<String, DevicePendingAuth>>>,
+    channels: Arc<Mutex<HashMap<String, Channel>>>,
+    change_events: broadcast::Sender<ChangeEvent>,
+    repository: Arc<dyn Repository>,
     scoring_config: Arc<RwLock<ScoringConfig>>,
     scoring_config_path: Option<PathBuf>,
 }
@@ -58,6 +75,62 @@ struct StreamEvent {
     event: String,
     message: String,
     timestamp_ms: u128,
+    seq: u64,
+}
+
+/// How many recent `StreamEvent`s a channel replays to a client that
+/// reconnects with a `Last-Event-ID` header.
+const STREAM_REPLAY_BUFFER: usize = 256;
+
+/// A single request's SSE fan-out: the live broadcast sender plus a bounded
+/// ring buffer of recent events so a client that drops and reconnects can
+/// replay what it missed instead of silently losing it. The buffer is a
+/// `std::sync::Mutex` rather than `tokio::sync::Mutex` because `send_event`
+/// is called from the synchronous `on_token` callback passed into
+/// `score_text_stream`, which can't hold an await point.
+#[derive(Clone)]
+struct Channel {
+    sender: broadcast::Sender<StreamEvent>,
+    buffer: Arc<std::sync::Mutex<VecDeque<StreamEvent>>>,
+    next_seq: Arc<AtomicU64>,
+}
+
+/// A server-wide mutation notification published on `AppState::change_events`
+/// and fanned out to every `/api/events` subscriber, modeled on a database
+/// LISTEN/NOTIFY trigger so dashboards see snapshot/profile/config changes
+/// without polling.
+#[derive(Clone, Serialize)]
+struct ChangeEvent {
+    kind: ChangeEventKind,
+    id: String,
+    timestamp_ms: u128,
+    payload: serde_json::Value,
+}
+
+#[derive(Clone, Copy, Serialize)]
+enum ChangeEventKind {
+    #[serde(rename = "snapshot.created")]
+    SnapshotCreated,
+    #[serde(rename = "snapshot.deleted")]
+    SnapshotDeleted,
+    #[serde(rename = "user.upserted")]
+    UserUpserted,
+    #[serde(rename = "weights.updated")]
+    WeightsUpdated,
+}
+
+fn publish_change(
+    sender: &broadcast::Sender<ChangeEvent>,
+    kind: ChangeEventKind,
+    id: impl Into<String>,
+    payload: serde_json::Value,
+) {
+    let _ = sender.send(ChangeEvent {
+        kind,
+        id: id.into(),
+        timestamp_ms: now_ms(),
+        payload,
+    });
 }
 
 #[derive(serde::Deserialize)]
@@ -68,6 +141,29 @@ struct StreamQuery {
 #[derive(Deserialize)]
 struct XProfileQuery {
     username: String,
+    request_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct XMeQuery {
+    request_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ReportOutcomeRequest {
+    impressions: u64,
+    likes: u64,
+    replies: u64,
+    reposts: u64,
+    quotes: Option<u64>,
+    shares: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct PlatformProfileQuery {
+    /// Which `SocialPlatform` to resolve `handle` against. Defaults to `"x"`.
+    platform: Option<String>,
+    handle: String,
 }
 
 #[derive(Serialize)]
@@ -89,10 +185,104 @@ struct XOAuthStatus {
     connected: bool,
 }
 
-#[derive(Clone)]
-struct XUserToken {
-    access_token: String,
-    expires_at: Instant,
+/// Connection detail for the currently linked X session, in the spirit of
+/// an OAuth `introspect` endpoint: enough for a client to show "connected as
+/// @foo, expires in 42m" and decide when to proactively refresh.
+#[derive(Serialize)]
+struct XOAuthIntrospection {
+    connected: bool,
+    scope: Vec<String>,
+    expires_at_ms: Option<u128>,
+    expires_in_seconds: Option<u64>,
+    has_refresh_token: bool,
+    user_id: Option<String>,
+    username: Option<String>,
+}
+
+impl XOAuthIntrospection {
+    fn disconnected() -> Self {
+        Self {
+            connected: false,
+            scope: Vec::new(),
+            expires_at_ms: None,
+            expires_in_seconds: None,
+            has_refresh_token: false,
+            user_id: None,
+            username: None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct XDeviceStartResponse {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    interval: u64,
+    expires_in: u64,
+}
+
+#[derive(Deserialize)]
+struct XDevicePollQuery {
+    device_code: String,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum XDevicePollResponse {
+    AuthorizationPending,
+    SlowDown,
+    Complete,
+}
+
+#[derive(Deserialize)]
+struct DeviceAuthorizationResponse {
+    device_code: String,
+    user_code: String,
+    verification_uri: Option<String>,
+    verification_url: Option<String>,
+    expires_in: u64,
+    interval: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct OAuthErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+/// Name of the HttpOnly cookie that maps a browser to a session in
+/// `AppState::token_store`, set by `x_oauth_callback` and cleared by
+/// `x_oauth_logout`.
+const X_SESSION_COOKIE: &str = "x_session";
+
+/// Refresh a session's access token this far ahead of `expires_at_ms` so a
+/// request never observes a token that expires mid-flight.
+const X_TOKEN_REFRESH_SKEW_MS: u128 = 60_000;
+
+/// How long a PKCE `state`/verifier pair from `x_oauth_start` stays valid
+/// before `x_oauth_callback` rejects it and the sweeper evicts it, so an
+/// abandoned authorization attempt can't be replayed and doesn't leak.
+const OAUTH_STATE_TTL_MS: u128 = 10 * 60 * 1000;
+
+/// A PKCE verifier issued by `x_oauth_start`, keyed by its `state` value.
+/// `created_at_ms` lets `x_oauth_callback` reject it once `OAUTH_STATE_TTL_MS`
+/// has passed.
+struct PendingOAuthState {
+    verifier: String,
+    created_at_ms: u128,
+}
+
+/// A device-code authorization in flight, tracked so `x_oauth_device_poll`
+/// can validate the caller's `device_code` and honor the authorization
+/// server's polling `interval` (bumped on a `slow_down` response) without
+/// hammering the token endpoint. `expires_at_ms` (from the authorization
+/// server's own `expires_in`) lets the sweeper evict it if the user never
+/// visits `verification_uri`, mirroring `PendingOAuthState`/`OAUTH_STATE_TTL_MS`.
+struct DevicePendingAuth {
+    interval: u64,
+    next_poll_at: Instant,
+    expires_at_ms: u128,
 }
 
 #[derive(Clone, Copy)]
@@ -104,23 +294,74 @@ enum OAuthAuthMode {
 static REQUEST_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
 pub async fn serve(args: crate::ServeArgs) -> Result<(), String> {
-    let snapshot_path = snapshot_path();
-    let snapshot_store = SnapshotStore::load(snapshot_path).await?;
-    let profiles_path = user_profiles_path();
-    let user_profiles = UserProfileStore::load(profiles_path).await?;
-    let (scoring_config, scoring_config_path) = ScoringConfig::load(None)?;
+    let (scoring_config, scoring_config_path, config_migrations) = ScoringConfig::load(None)?;
+    for migration in &config_migrations {
+        tracing::info!(
+            from = migration.from_version,
+            to = migration.to_version,
+            description = migration.description,
+            "migrated scoring config schema"
+        );
+    }
+
+    let database_url = args
+        .database_url
+        .clone()
+        .or_else(|| std::env::var("DATABASE_URL").ok());
+    let repository: Arc<dyn Repository> = match database_url {
+        Some(url) => {
+            tracing::info!("using postgres repository backend");
+            Arc::new(PostgresRepository::connect(&url, snapshot_retention()).await?)
+        }
+        None => {
+            tracing::info!("using file repository backend");
+            Arc::new(
+                FileRepository::open_with_retention(
+                    snapshot_path(),
+                    user_profiles_path(),
+                    scoring_config_path.clone(),
+                    snapshot_retention(),
+                )
+                .await?,
+            )
+        }
+    };
+    let trace_store = match TraceStore::open(trace_store_path()).await {
+        Ok(store) => Some(Arc::new(store)),
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to open trace store; LLM traces won't be persisted");
+            None
+        }
+    };
+    let token_store = Arc::new(TokenStore::load(token_store_path()).await?);
     let state = AppState {
-        llm_client: LlmClient::from_env(None),
+        llm_client: llm::from_env(None).map(Arc::from),
+        embeddings_client: crate::embeddings::EmbeddingsClient::from_env().map(Arc::new),
+        trace_store,
         x_client: XApiClient::from_env(),
-        x_user_token: Arc::new(Mutex::new(None)),
+        mastodon_client: MastodonClient::from_env(),
+        token_store,
         oauth_state: Arc::new(Mutex::new(HashMap::new())),
+        device_codes: Arc::new(Mutex::new(HashMap::new())),
         channels: Arc::new(Mutex::new(HashMap::new())),
-        snapshots: Arc::new(snapshot_store),
-        user_profiles: Arc::new(user_profiles),
+        change_events: broadcast::channel(128).0,
+        repository,
         scoring_config: Arc::new(RwLock::new(scoring_config)),
         scoring_config_path,
     };
 
+    // Kept alive for the rest of `serve`'s lifetime (effectively the process
+    // lifetime): dropping it would stop the filesystem watch.
+    let _config_watcher = state.scoring_config_path.clone().and_then(|path| {
+        match virality_sim::config_watcher::ScoringConfigWatcher::spawn(state.scoring_config.clone(), path) {
+            Ok(watcher) => Some(watcher),
+            Err(err) => {
+                tracing::warn!(error = %err, "failed to start scoring config watcher");
+                None
+            }
+        }
+    });
+
     let web_root = args.web_root;
     let index_path = format!("{}/index.html", web_root.trim_end_matches('/'));
     let static_service = ServeDir::new(web_root).not_found_service(ServeFile::new(index_path));
@@ -131,17 +372,28 @@ pub async fn serve(args: crate::ServeArgs) -> Result<(), String> {
         .route("/api/simulate/phoenix", post(simulate_phoenix_handler))
         .route("/api/simulate/compare", post(compare_handler))
         .route("/api/simulate/stream", get(stream_handler))
+        .route("/api/events", get(events_handler))
         .route("/api/config", get(get_config))
         .route("/api/config/weights", put(update_weights))
         .route("/api/users", post(upsert_user))
         .route("/api/users/:user_id/history", get(get_user_history))
+        .route("/api/platform/profile", get(platform_profile_handler))
         .route("/api/x/profile", get(x_profile_handler))
         .route("/api/x/me", get(x_me_handler))
         .route("/api/x/oauth/start", get(x_oauth_start))
         .route("/api/x/oauth/callback", get(x_oauth_callback))
         .route("/api/x/oauth/status", get(x_oauth_status))
+        .route("/api/x/oauth/introspect", get(x_oauth_introspect))
+        .route("/api/x/oauth/logout", post(x_oauth_logout))
+        .route("/api/x/oauth/device/start", post(x_oauth_device_start))
+        .route("/api/x/oauth/device/poll", get(x_oauth_device_poll))
         .route("/api/snapshots", get(list_snapshots).post(create_snapshot))
+        .route("/api/snapshots/query", get(query_snapshots))
+        .route("/api/snapshots/raw", post(create_snapshot_raw))
         .route("/api/snapshots/:id", get(get_snapshot).delete(delete_snapshot))
+        .route("/v1/chat/completions", post(chat_completions_handler))
+        .route("/v1/completions", post(completions_handler))
+        .route("/api/traces/:id/outcome", post(record_trace_outcome))
         .nest_service("/", static_service)
         .with_state(state);
 
@@ -158,10 +410,25 @@ pub async fn serve(args: crate::ServeArgs) -> Result<(), String> {
     Ok(())
 }
 
-async fn health() -> impl IntoResponse {
-    StatusCode::OK
+async fn health(State(state): State<AppState>) -> impl IntoResponse {
+    match state.repository.health_check().await {
+        Ok(()) => StatusCode::OK,
+        Err(err) => {
+            tracing::warn!(error = %err, "health check failed");
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+    }
 }
 
+#[tracing::instrument(
+    skip(state, request),
+    fields(
+        followers = request.followers.unwrap_or_default(),
+        media = %request.media.clone().unwrap_or_else(|| "none".to_string()),
+        mode = %request.scoring_mode.clone().unwrap_or_else(|| "heuristic".to_string()),
+        score = tracing::field::Empty,
+    )
+)]
 async fn simulate_handler(
     State(state): State<AppState>,
     Json(request): Json<ApiSimulationRequest>,
@@ -180,46 +447,47 @@ async fn simulate_handler(
     let mut progress_done: Option<Arc<AtomicBool>> = None;
 
     let mut warnings = Vec::new();
-    let llm_result = if use_ai {
+    let mut llm_result = if use_ai {
         if let Some(sender) = channel.as_ref() {
-            send_event(sender, "start", "Preparing Grok prompt");
+            send_event(sender, "start", "Preparing AI prompt");
         }
         match &state.llm_client {
             Some(client) => {
                 if let Some(sender) = channel.as_ref() {
-                    let prompt = prompt_for_text(&input.text);
+                    let prompt = llm::prompt_for_text(&input.text);
                     send_event(sender, "prompt", &prompt);
-                    send_event(sender, "calling", "Calling Grok API");
+                    send_event(sender, "calling", "Calling AI scoring backend");
                     progress_done = Some(start_progress(sender.clone()));
                 }
                 let result = if let Some(sender) = channel.as_ref() {
                     let token_sender = sender.clone();
-                    client
-                        .score_text_stream(&input.text, |chunk| {
-                            send_event(&token_sender, "token", chunk);
-                        })
-                        .await
+                    let mut on_token = |chunk: &str| {
+                        send_event(&token_sender, "token", chunk);
+                    };
+                    client.score_text_stream(&input.text, &mut on_token).await
                 } else {
                     client.score_text(&input.text).await
                 };
                 match result {
                     Ok(result) => {
                         if let Some(sender) = channel.as_ref() {
-                            send_event(sender, "received", "Received Grok response");
+                            send_event(sender, "received", "Received AI response");
                         }
                         Some(result)
                     }
                     Err(err) => {
                         warnings.push(format!("AI scoring failed: {}", err));
                         if let Some(sender) = channel.as_ref() {
-                            send_event(sender, "error", "Grok call failed");
+                            send_event(sender, "error", "AI call failed");
                         }
                         None
                     }
                 }
             }
             None => {
-                warnings.push("AI scoring not configured: set XAI_API_KEY".to_string());
+                warnings.push(
+                    "AI scoring not configured: set SCORER_PROVIDER and its API key".to_string(),
+                );
                 if let Some(sender) = channel.as_ref() {
                     send_event(sender, "error", "AI scoring not configured");
                 }
@@ -230,12 +498,49 @@ async fn simulate_handler(
         None
     };
 
+    if let Some(result) = llm_result.as_mut() {
+        if let Some(corpus) = request.corpus.as_ref().filter(|corpus| !corpus.is_empty()) {
+            match &state.embeddings_client {
+                Some(embeddings) => {
+                    if let Err(err) =
+                        embeddings.apply_corpus_novelty(result, &input.text, corpus).await
+                    {
+                        warnings.push(format!("Corpus novelty scoring failed: {}", err));
+                    }
+                }
+                None => warnings.push(
+                    "Corpus novelty scoring not configured: set XAI_API_KEY or OPENAI_API_KEY"
+                        .to_string(),
+                ),
+            }
+        }
+    }
+
     if let Some(done_flag) = progress_done {
         done_flag.store(true, Ordering::Relaxed);
     }
 
+    let mut trace_id = None;
+    if let (Some(result), Some(store)) = (llm_result.as_ref(), state.trace_store.as_ref()) {
+        let context = TraceContext {
+            post_id: request.post_id.as_ref().and_then(|id| PostId::new(id).ok()),
+            post_text: input.text.clone(),
+            author_followers: input.followers,
+            author_following: Some(input.following),
+            account_age_days: Some(input.account_age_days),
+            avg_engagement_rate: Some(input.avg_engagement_rate),
+            posts_per_day: Some(input.posts_per_day),
+            verified: Some(input.verified),
+            media_type: request.media.clone().unwrap_or_else(|| "none".to_string()),
+        };
+        match store.record_trace(&context, &result.score, &result.trace).await {
+            Ok(id) => trace_id = Some(id),
+            Err(err) => warnings.push(format!("Failed to persist LLM trace: {}", err)),
+        }
+    }
+
     if let Some(sender) = channel.as_ref() {
-        send_event(sender, "merge", "Merging Grok signals into model");
+        send_event(sender, "merge", "Merging AI signals into model");
     }
 
     let scoring_config = state.scoring_config.read().await.clone();
@@ -267,12 +572,14 @@ async fn simulate_handler(
         phoenix_actions.as_ref(),
         &scoring_config,
     );
+    tracing::Span::current().record("score", output.score);
     if let Some(sender) = channel.as_ref() {
         send_event(sender, "done", "Simulation complete");
         schedule_cleanup(state.channels.clone(), request_id.clone());
     }
 
-    let response = ApiSimulationResponse::from_output(output, warnings, request_id);
+    let mut response = ApiSimulationResponse::from_output(output, warnings, request_id);
+    response.trace_id = trace_id;
     Ok(Json(response))
 }
 
@@ -339,7 +646,10 @@ async fn compare_handler(
                     }
                 },
                 None => {
-                    warnings.push("AI scoring not configured: set XAI_API_KEY".to_string());
+                    warnings.push(
+                        "AI scoring not configured: set SCORER_PROVIDER and its API key"
+                            .to_string(),
+                    );
                     None
                 }
             }
@@ -420,6 +730,18 @@ async fn update_weights(
             .write(path)
             .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
     }
+    state
+        .repository
+        .save_weights(&config.weights)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
+
+    publish_change(
+        &state.change_events,
+        ChangeEventKind::WeightsUpdated,
+        "weights",
+        serde_json::to_value(&config.weights).unwrap_or_default(),
+    );
 
     Ok(Json(config.clone()))
 }
@@ -440,14 +762,16 @@ async fn upsert_user(
     State(state): State<AppState>,
     Json(request): Json<UserProfileRequest>,
 ) -> Result<Json<UserProfile>, (StatusCode, String)> {
-    let user_id = request.user_id.trim();
-    if user_id.is_empty() {
-        return Err((StatusCode::BAD_REQUEST, "user_id is required".to_string()));
-    }
+    let user_id = UserId::new(request.user_id.trim())
+        .map_err(|err| (StatusCode::BAD_REQUEST, err))?;
 
-    let existing = state.user_profiles.get(user_id).await;
+    let existing = state
+        .repository
+        .get_profile(user_id.as_str())
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
     let mut profile = existing.unwrap_or(UserProfile {
-        user_id: user_id.to_string(),
+        user_id: user_id.clone(),
         followers: request.followers.unwrap_or(0),
         following: request.following.unwrap_or(0),
         account_age_days: request.account_age_days.unwrap_or(0),
@@ -473,16 +797,23 @@ async fn upsert_user(
     if request.generate_synthetic_history.unwrap_or(false) {
         let seed = request
             .synthetic_seed
-            .unwrap_or_else(|| stable_hash64(user_id));
+            .unwrap_or_else(|| stable_hash64(user_id.as_str()));
         profile.engagement_history = generate_synthetic_history(&profile, seed);
     }
 
     let saved = state
-        .user_profiles
-        .upsert(profile)
+        .repository
+        .upsert_profile(profile)
         .await
         .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
 
+    publish_change(
+        &state.change_events,
+        ChangeEventKind::UserUpserted,
+        saved.user_id.as_str(),
+        serde_json::to_value(&saved).unwrap_or_default(),
+    );
+
     Ok(Json(saved))
 }
 
@@ -490,7 +821,12 @@ async fn get_user_history(
     State(state): State<AppState>,
     axum::extract::Path(user_id): axum::extract::Path<String>,
 ) -> Result<Json<Vec<EngagementEvent>>, (StatusCode, String)> {
-    match state.user_profiles.get(&user_id).await {
+    match state
+        .repository
+        .get_profile(&user_id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?
+    {
         Some(profile) => Ok(Json(profile.engagement_history)),
         None => Err((StatusCode::NOT_FOUND, "user not found".to_string())),
     }
@@ -541,10 +877,10 @@ async fn fetch_phoenix_actions(
     request: &ApiSimulationRequest,
 ) -> Result<ActionProbs, String> {
     let client = PhoenixClient::from_config(scoring_config)?;
-    let (post_id, author_id) = ensure_candidate_ids(input);
+    let (post_id, author_id) = ensure_candidate_ids(input)?;
     let candidate = build_post_features(input, &post_id, &author_id);
 
-    let user_id = derive_user_id(input, request);
+    let user_id = derive_user_id(input, request)?;
     let (history_posts, history_actions) =
         build_history(state, &user_id, input, scoring_config.phoenix.history_limit).await?;
     let ranking_request = RankingRequest {
@@ -567,24 +903,24 @@ async fn fetch_phoenix_actions(
 
 async fn build_history(
     state: &AppState,
-    user_id: &str,
+    user_id: &UserId,
     input: &SimulatorInput,
     history_limit: usize,
 ) -> Result<(Vec<PostFeatures>, Vec<Vec<f32>>), String> {
-    let mut profile = state.user_profiles.get(user_id).await;
-    let seed = stable_hash64(user_id);
+    let mut profile = state.repository.get_profile(user_id.as_str()).await?;
+    let seed = stable_hash64(user_id.as_str());
 
     if profile.is_none() {
-        let mut new_profile = profile_from_input(user_id.to_string(), input);
+        let mut new_profile = profile_from_input(user_id.clone(), input);
         new_profile.engagement_history = generate_synthetic_history(&new_profile, seed);
-        state.user_profiles.upsert(new_profile.clone()).await?;
+        state.repository.upsert_profile(new_profile.clone()).await?;
         profile = Some(new_profile);
     }
 
     let mut profile = profile.expect("profile must be initialized");
     if profile.engagement_history.is_empty() {
         profile.engagement_history = generate_synthetic_history(&profile, seed);
-        state.user_profiles.upsert(profile.clone()).await?;
+        state.repository.upsert_profile(profile.clone()).await?;
     }
 
     let history = if profile.engagement_history.len() > history_limit {
@@ -599,8 +935,8 @@ async fn build_history(
         .map(|event| PostFeatures {
             post_id: event.post_id.clone(),
             author_id: event.author_id.clone(),
-            text_hash: stable_hash64(&event.post_id),
-            author_hash: stable_hash64(&event.author_id),
+            text_hash: stable_hash64(event.post_id.as_str()),
+            author_hash: stable_hash64(event.author_id.as_str()),
             product_surface: 0,
             video_duration_seconds: None,
         })
@@ -614,7 +950,7 @@ async fn build_history(
     Ok((history_posts, history_actions))
 }
 
-fn ensure_candidate_ids(input: &mut SimulatorInput) -> (String, String) {
+fn ensure_candidate_ids(input: &mut SimulatorInput) -> Result<(PostId, AuthorId), String> {
     let post_id = input.post_id.clone().unwrap_or_else(|| {
         let hash = stable_hash64(&input.text);
         format!("post_{:x}", hash)
@@ -635,16 +971,16 @@ fn ensure_candidate_ids(input: &mut SimulatorInput) -> (String, String) {
         input.author_id = Some(author_id.clone());
     }
 
-    (post_id, author_id)
+    Ok((post_id.try_into()?, author_id.try_into()?))
 }
 
 fn build_post_features(
     input: &SimulatorInput,
-    post_id: &str,
-    author_id: &str,
+    post_id: &PostId,
+    author_id: &AuthorId,
 ) -> PostFeatures {
     let video_duration_seconds = input.video_duration_seconds.or_else(|| {
-        if matches!(input.media, MediaType::Video) {
+        if matches!(input.media, MediaType::Video { is_live: false }) {
             Some(15.0)
         } else {
             None
@@ -652,17 +988,20 @@ fn build_post_features(
     });
 
     PostFeatures {
-        post_id: post_id.to_string(),
-        author_id: author_id.to_string(),
+        post_id: post_id.clone(),
+        author_id: author_id.clone(),
         text_hash: stable_hash64(&input.text),
-        author_hash: stable_hash64(author_id),
+        author_hash: stable_hash64(author_id.as_str()),
         product_surface: 0,
         video_duration_seconds,
     }
 }
 
-fn derive_user_id(input: &SimulatorInput, request: &ApiSimulationRequest) -> String {
-    request
+fn derive_user_id(
+    input: &SimulatorInput,
+    request: &ApiSimulationRequest,
+) -> Result<UserId, String> {
+    let raw = request
         .user_id
         .clone()
         .filter(|value| !value.trim().is_empty())
@@ -672,7 +1011,8 @@ fn derive_user_id(input: &SimulatorInput, request: &ApiSimulationRequest) -> Str
                 input.followers, input.following, input.account_age_days
             );
             format!("user_{:x}", stable_hash64(&payload))
-        })
+        });
+    raw.try_into()
 }
 
 fn stable_hash64(value: &str) -> u64 {
@@ -693,7 +1033,11 @@ struct SnapshotRequest {
 }
 
 async fn list_snapshots(State(state): State<AppState>) -> Result<Json<Vec<Snapshot>>, StatusCode> {
-    let snapshots = state.snapshots.list().await;
+    let snapshots = state
+        .repository
+        .list_snapshots()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     Ok(Json(snapshots))
 }
 
@@ -701,7 +1045,12 @@ async fn get_snapshot(
     State(state): State<AppState>,
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<Json<Snapshot>, (StatusCode, String)> {
-    match state.snapshots.get(&id).await {
+    match state
+        .repository
+        .get_snapshot(&id)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?
+    {
         Some(snapshot) => Ok(Json(snapshot)),
         None => Err((StatusCode::NOT_FOUND, "Snapshot not found".to_string())),
     }
@@ -720,10 +1069,92 @@ async fn create_snapshot(
         output: payload.output,
     };
     let saved = state
-        .snapshots
-        .add(snapshot)
+        .repository
+        .add_snapshot(snapshot)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
+
+    publish_change(
+        &state.change_events,
+        ChangeEventKind::SnapshotCreated,
+        saved.id.clone(),
+        serde_json::to_value(&saved).unwrap_or_default(),
+    );
+
+    Ok(Json(saved))
+}
+
+#[derive(Deserialize)]
+struct SnapshotQueryParams {
+    created_after: Option<String>,
+    created_before: Option<String>,
+    contains: Option<String>,
+    field: Option<String>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+}
+
+async fn query_snapshots(
+    State(state): State<AppState>,
+    Query(params): Query<SnapshotQueryParams>,
+) -> Result<Json<SnapshotQueryResult>, StatusCode> {
+    let query = SnapshotQuery {
+        created_after: params.created_after,
+        created_before: params.created_before,
+        contains: params.contains,
+        field: params.field,
+        offset: params.offset.unwrap_or(0),
+        limit: params.limit.unwrap_or(0),
+    };
+    let result = state
+        .repository
+        .query_snapshots(query)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(result))
+}
+
+/// Ingests a snapshot whose `output` is the raw request body, streamed
+/// straight into the repository instead of parsed into memory here first.
+/// `id`/`created_at`/`input` ride along as headers since they're assumed
+/// small (the parameters that produced `output`, not the payload itself).
+async fn create_snapshot_raw(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: axum::body::Body,
+) -> Result<Json<Snapshot>, (StatusCode, String)> {
+    let id = headers
+        .get("x-snapshot-id")
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+        .unwrap_or_else(generate_snapshot_id);
+    let created_at = headers
+        .get("x-snapshot-created-at")
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+        .unwrap_or_else(chrono_like_timestamp);
+    let input: serde_json::Value = match headers.get("x-snapshot-input").and_then(|value| value.to_str().ok()) {
+        Some(value) => serde_json::from_str(value)
+            .map_err(|err| (StatusCode::BAD_REQUEST, format!("invalid x-snapshot-input header: {}", err)))?,
+        None => serde_json::Value::Null,
+    };
+
+    let output_stream: RawByteStream =
+        Box::pin(body.into_data_stream().map(|chunk| chunk.map_err(|err| err.to_string())));
+
+    let saved = state
+        .repository
+        .add_snapshot_raw(id, created_at, input, output_stream)
         .await
         .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
+
+    publish_change(
+        &state.change_events,
+        ChangeEventKind::SnapshotCreated,
+        saved.id.clone(),
+        serde_json::to_value(&saved).unwrap_or_default(),
+    );
+
     Ok(Json(saved))
 }
 
@@ -732,40 +1163,322 @@ async fn delete_snapshot(
     axum::extract::Path(id): axum::extract::Path<String>,
 ) -> Result<StatusCode, (StatusCode, String)> {
     let removed = state
-        .snapshots
-        .delete(&id)
+        .repository
+        .delete_snapshot(&id)
         .await
         .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
     if removed {
+        publish_change(
+            &state.change_events,
+            ChangeEventKind::SnapshotDeleted,
+            id,
+            serde_json::Value::Null,
+        );
         Ok(StatusCode::NO_CONTENT)
     } else {
         Err((StatusCode::NOT_FOUND, "Snapshot not found".to_string()))
     }
 }
 
+async fn record_trace_outcome(
+    State(state): State<AppState>,
+    axum::extract::Path(trace_id): axum::extract::Path<i64>,
+    Json(request): Json<ReportOutcomeRequest>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    let store = state
+        .trace_store
+        .as_ref()
+        .ok_or_else(|| (StatusCode::SERVICE_UNAVAILABLE, "trace store not configured".to_string()))?;
+    let outcome = ObservedOutcome {
+        impressions: request.impressions,
+        likes: request.likes,
+        replies: request.replies,
+        reposts: request.reposts,
+        quotes: request.quotes,
+        shares: request.shares,
+    };
+    store
+        .record_outcome(trace_id, &outcome)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
 async fn stream_handler(
     State(state): State<AppState>,
     Query(query): Query<StreamQuery>,
+    headers: HeaderMap,
 ) -> Result<Sse<impl tokio_stream::Stream<Item = Result<Event, std::convert::Infallible>>>, StatusCode>
 {
-    let sender = get_or_create_channel(&state, &query.request_id).await;
-    let receiver = sender.subscribe();
-    let stream = BroadcastStream::new(receiver).filter_map(|event| {
-        match event {
-            Ok(event) => {
-                let data = serde_json::to_string(&event).unwrap_or_default();
-                Some(Ok(Event::default().data(data)))
-            }
-            Err(_) => None,
+    let channel = get_or_create_channel(&state, &query.request_id).await;
+    let receiver = channel.sender.subscribe();
+
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    let replay: Vec<StreamEvent> = match last_event_id {
+        Some(last_seq) => {
+            let buffer = channel.buffer.lock().unwrap_or_else(|err| err.into_inner());
+            buffer
+                .iter()
+                .filter(|event| event.seq > last_seq)
+                .cloned()
+                .collect()
         }
+        None => Vec::new(),
+    };
+
+    let replay_stream = tokio_stream::iter(
+        replay
+            .into_iter()
+            .map(|event| Ok(to_sse_event(&event)) as Result<Event, std::convert::Infallible>),
+    );
+    let live_stream = BroadcastStream::new(receiver).filter_map(|event| match event {
+        Ok(event) => Some(Ok(to_sse_event(&event))),
+        Err(_) => None,
     });
+    let stream = replay_stream.chain(live_stream);
 
-    send_event(&sender, "connected", "Streaming Grok status");
+    send_event(&channel, "connected", "Streaming AI scoring status");
     Ok(Sse::new(stream).keep_alive(KeepAlive::new().interval(Duration::from_secs(8))))
 }
 
+/// Renders a `StreamEvent` as an SSE frame with its sequence number set as
+/// the event `id`, so browsers report it back via `Last-Event-ID` on
+/// reconnect and `stream_handler` can replay only what was missed.
+fn to_sse_event(event: &StreamEvent) -> Event {
+    let data = serde_json::to_string(event).unwrap_or_default();
+    Event::default().id(event.seq.to_string()).data(data)
+}
+
+/// Server-wide feed of snapshot/profile/config mutations, fanned out to every
+/// subscriber from `AppState::change_events`. Unlike `stream_handler`, which
+/// is scoped to a single simulation run, this carries every `ChangeEvent`
+/// published anywhere on the server.
+async fn events_handler(
+    State(state): State<AppState>,
+) -> Sse<impl tokio_stream::Stream<Item = Result<Event, std::convert::Infallible>>> {
+    let receiver = state.change_events.subscribe();
+    let stream = BroadcastStream::new(receiver).filter_map(|event| match event {
+        Ok(event) => {
+            let data = serde_json::to_string(&event).unwrap_or_default();
+            Some(Ok(Event::default().data(data)))
+        }
+        Err(_) => None,
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::new().interval(Duration::from_secs(8)))
+}
+
+async fn chat_completions_handler(
+    State(state): State<AppState>,
+    Json(request): Json<ChatCompletionRequest>,
+) -> Result<axum::response::Response, (StatusCode, Json<OpenAiError>)> {
+    let text = request
+        .messages
+        .iter()
+        .rev()
+        .find(|message| message.role == "user")
+        .map(|message| message.content.clone())
+        .ok_or_else(|| {
+            openai_compat::openai_error(StatusCode::BAD_REQUEST, "messages must include a user turn")
+        })?;
+
+    let client = scoring_backend(&state)?;
+    let model = request.model.unwrap_or_else(|| "virality-scorer".to_string());
+    let id = openai_compat::completion_id("chatcmpl");
+
+    if request.stream.unwrap_or(false) {
+        return Ok(stream_chat_completion(client, text, id, model).into_response());
+    }
+
+    let result = client
+        .score_text(&text)
+        .await
+        .map_err(|err| openai_compat::openai_error(StatusCode::BAD_GATEWAY, &err))?;
+    let content = serde_json::to_string(&result.score).unwrap_or_default();
+
+    Ok(Json(ChatCompletionResponse {
+        id,
+        object: "chat.completion",
+        created: openai_compat::now_unix(),
+        model,
+        choices: vec![ChatCompletionChoice {
+            index: 0,
+            message: ChatMessage {
+                role: "assistant".to_string(),
+                content,
+            },
+            finish_reason: "stop",
+        }],
+        usage: openai_compat::usage_from_trace(&result.trace),
+    })
+    .into_response())
+}
+
+async fn completions_handler(
+    State(state): State<AppState>,
+    Json(request): Json<CompletionRequest>,
+) -> Result<axum::response::Response, (StatusCode, Json<OpenAiError>)> {
+    let text = request
+        .prompt
+        .first()
+        .filter(|text| !text.trim().is_empty())
+        .ok_or_else(|| openai_compat::openai_error(StatusCode::BAD_REQUEST, "prompt is required"))?
+        .to_string();
+
+    let client = scoring_backend(&state)?;
+    let model = request.model.unwrap_or_else(|| "virality-scorer".to_string());
+    let id = openai_compat::completion_id("cmpl");
+
+    if request.stream.unwrap_or(false) {
+        return Ok(stream_chat_completion(client, text, id, model).into_response());
+    }
+
+    let result = client
+        .score_text(&text)
+        .await
+        .map_err(|err| openai_compat::openai_error(StatusCode::BAD_GATEWAY, &err))?;
+    let content = serde_json::to_string(&result.score).unwrap_or_default();
+
+    Ok(Json(CompletionResponse {
+        id,
+        object: "text_completion",
+        created: openai_compat::now_unix(),
+        model,
+        choices: vec![CompletionChoice {
+            index: 0,
+            text: content,
+            finish_reason: "stop",
+        }],
+        usage: openai_compat::usage_from_trace(&result.trace),
+    })
+    .into_response())
+}
+
+fn scoring_backend(
+    state: &AppState,
+) -> Result<Arc<dyn ScoringBackend>, (StatusCode, Json<OpenAiError>)> {
+    state.llm_client.clone().ok_or_else(|| {
+        openai_compat::openai_error(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "AI scoring not configured: set SCORER_PROVIDER and its API key",
+        )
+    })
+}
+
+/// Streams tokens as they arrive from `score_text_stream`, re-shaping each
+/// one into a `chat.completion.chunk` delta, then emits a final chunk
+/// carrying `finish_reason: "stop"` before the `[DONE]` sentinel, matching
+/// the OpenAI streaming protocol byte-for-byte.
+fn stream_chat_completion(
+    client: Arc<dyn ScoringBackend>,
+    text: String,
+    id: String,
+    model: String,
+) -> Sse<impl tokio_stream::Stream<Item = Result<Event, std::convert::Infallible>>> {
+    let (tx, rx) = tokio::sync::mpsc::channel::<String>(64);
+    let created = openai_compat::now_unix();
+
+    tokio::spawn(async move {
+        let role_chunk = ChatCompletionChunk {
+            id: id.clone(),
+            object: "chat.completion.chunk",
+            created,
+            model: model.clone(),
+            choices: vec![ChatCompletionChunkChoice {
+                index: 0,
+                delta: ChatCompletionDelta {
+                    role: Some("assistant"),
+                    content: None,
+                },
+                finish_reason: None,
+            }],
+        };
+        let _ = tx
+            .send(serde_json::to_string(&role_chunk).unwrap_or_default())
+            .await;
+
+        let tx_tokens = tx.clone();
+        let id_tokens = id.clone();
+        let model_tokens = model.clone();
+        let mut on_token = move |chunk: &str| {
+            let delta_chunk = ChatCompletionChunk {
+                id: id_tokens.clone(),
+                object: "chat.completion.chunk",
+                created,
+                model: model_tokens.clone(),
+                choices: vec![ChatCompletionChunkChoice {
+                    index: 0,
+                    delta: ChatCompletionDelta {
+                        role: None,
+                        content: Some(chunk.to_string()),
+                    },
+                    finish_reason: None,
+                }],
+            };
+            let _ = tx_tokens.try_send(serde_json::to_string(&delta_chunk).unwrap_or_default());
+        };
+
+        if client.score_text_stream(&text, &mut on_token).await.is_ok() {
+            let final_chunk = ChatCompletionChunk {
+                id,
+                object: "chat.completion.chunk",
+                created,
+                model,
+                choices: vec![ChatCompletionChunkChoice {
+                    index: 0,
+                    delta: ChatCompletionDelta::default(),
+                    finish_reason: Some("stop"),
+                }],
+            };
+            let _ = tx
+                .send(serde_json::to_string(&final_chunk).unwrap_or_default())
+                .await;
+        }
+        let _ = tx.send("[DONE]".to_string()).await;
+    });
+
+    let stream = ReceiverStream::new(rx).map(|data| Ok(Event::default().data(data)));
+    Sse::new(stream).keep_alive(KeepAlive::new().interval(Duration::from_secs(8)))
+}
+
+async fn platform_profile_handler(
+    State(state): State<AppState>,
+    Query(query): Query<PlatformProfileQuery>,
+) -> Result<Json<PlatformProfile>, (StatusCode, String)> {
+    let handle = query.handle.trim();
+    if handle.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "handle is required".to_string()));
+    }
+
+    let platform = query.platform.as_deref().unwrap_or("x");
+    let profile = match platform {
+        "mastodon" => {
+            let client = state
+                .mastodon_client
+                .as_ref()
+                .ok_or_else(|| (StatusCode::BAD_REQUEST, "Mastodon not configured".to_string()))?;
+            client.fetch_user(handle).await
+        }
+        "x" => {
+            let client = state
+                .x_client
+                .as_ref()
+                .ok_or_else(|| (StatusCode::BAD_REQUEST, "X API not configured".to_string()))?;
+            client.fetch_user(handle).await
+        }
+        other => return Err((StatusCode::BAD_REQUEST, format!("unknown platform: {}", other))),
+    }
+    .map_err(|err| (StatusCode::BAD_GATEWAY, err))?;
+
+    Ok(Json(profile))
+}
+
 async fn x_profile_handler(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Query(query): Query<XProfileQuery>,
 ) -> Result<Json<XUserProfile>, (StatusCode, String)> {
     let username = query.username.trim();
@@ -777,17 +1490,28 @@ async fn x_profile_handler(
         .x_client
         .as_ref()
         .ok_or_else(|| (StatusCode::BAD_REQUEST, "X API not configured".to_string()))?;
+    let channel = match query.request_id.as_deref() {
+        Some(request_id) => Some(get_or_create_channel(&state, request_id).await),
+        None => None,
+    };
+    let mut on_rate_limited = |message: &str| {
+        if let Some(sender) = channel.as_ref() {
+            send_event(sender, "rate_limited", message);
+        }
+    };
 
-    if let Some(token) = get_user_token(&state).await {
-        let profile = client
-            .fetch_user_by_username_with_token(username, &token)
-            .await
-            .map_err(|err| (StatusCode::BAD_GATEWAY, err))?;
-        return Ok(Json(profile));
+    if let Some(session_id) = session_id_from_headers(&headers) {
+        if let Some(token) = get_user_token(&state, &session_id).await {
+            let profile = client
+                .fetch_user_by_username_with_token_on(username, &token, &mut on_rate_limited)
+                .await
+                .map_err(|err| (StatusCode::BAD_GATEWAY, err))?;
+            return Ok(Json(profile));
+        }
     }
 
     let profile = client
-        .fetch_user_by_username(username)
+        .fetch_user_by_username_on(username, &mut on_rate_limited)
         .await
         .map_err(|err| (StatusCode::BAD_GATEWAY, err))?;
 
@@ -796,17 +1520,30 @@ async fn x_profile_handler(
 
 async fn x_me_handler(
     State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<XMeQuery>,
 ) -> Result<Json<XUserProfile>, (StatusCode, String)> {
     let client = state
         .x_client
         .as_ref()
         .ok_or_else(|| (StatusCode::BAD_REQUEST, "X API not configured".to_string()))?;
-    let token = get_user_token(&state)
+    let session_id = session_id_from_headers(&headers)
+        .ok_or_else(|| (StatusCode::UNAUTHORIZED, "X OAuth not connected".to_string()))?;
+    let token = get_user_token(&state, &session_id)
         .await
         .ok_or_else(|| (StatusCode::UNAUTHORIZED, "X OAuth not connected".to_string()))?;
+    let channel = match query.request_id.as_deref() {
+        Some(request_id) => Some(get_or_create_channel(&state, request_id).await),
+        None => None,
+    };
+    let mut on_rate_limited = |message: &str| {
+        if let Some(sender) = channel.as_ref() {
+            send_event(sender, "rate_limited", message);
+        }
+    };
 
     let profile = client
-        .fetch_me_with_token(&token)
+        .fetch_me_with_token_on(&token, &mut on_rate_limited)
         .await
         .map_err(|err| (StatusCode::BAD_GATEWAY, err))?;
 
@@ -825,8 +1562,15 @@ async fn x_oauth_start(
 
     {
         let mut guard = state.oauth_state.lock().await;
-        guard.insert(state_value.clone(), verifier);
+        guard.insert(
+            state_value.clone(),
+            PendingOAuthState {
+                verifier,
+                created_at_ms: now_ms(),
+            },
+        );
     }
+    schedule_oauth_state_cleanup(state.oauth_state.clone(), state_value.clone());
 
     let auth_url = format!(
         "https://twitter.com/i/oauth2/authorize?response_type=code&client_id={}&redirect_uri={}&scope={}&state={}&code_challenge={}&code_challenge_method=S256",
@@ -857,57 +1601,302 @@ async fn x_oauth_callback(
         .state
         .ok_or_else(|| (StatusCode::BAD_REQUEST, "Missing OAuth state".to_string()))?;
 
-    let verifier = {
+    let pending = {
         let mut guard = state.oauth_state.lock().await;
         guard.remove(&state_value)
     }
     .ok_or_else(|| (StatusCode::BAD_REQUEST, "Invalid OAuth state".to_string()))?;
 
+    if now_ms().saturating_sub(pending.created_at_ms) > OAUTH_STATE_TTL_MS {
+        return Err((StatusCode::BAD_REQUEST, "OAuth state expired".to_string()));
+    }
+    let verifier = pending.verifier;
+
     let config = oauth_config()
         .ok_or_else(|| (StatusCode::BAD_REQUEST, "X OAuth not configured".to_string()))?;
 
-    let token = exchange_oauth_code(&config, &code, &verifier)
+    let mut token = exchange_oauth_code(&config, &code, &verifier)
         .await
         .map_err(|err| (StatusCode::BAD_GATEWAY, err))?;
 
-    {
-        let mut guard = state.x_user_token.lock().await;
-        *guard = Some(token);
+    if let Some(client) = state.x_client.as_ref() {
+        match client.fetch_me_with_token(&token.access_token).await {
+            Ok(profile) => {
+                token.x_user_id = Some(profile.id);
+                token.x_username = Some(profile.username);
+            }
+            Err(err) => {
+                tracing::warn!(error = %err, "failed to fetch X profile for new OAuth session");
+            }
+        }
     }
 
-    Ok(axum::response::Redirect::to("/?x_oauth=success"))
+    let session_id = random_token(24);
+    state
+        .token_store
+        .insert(session_id.clone(), token)
+        .await
+        .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
+
+    let mut response = axum::response::Redirect::to("/?x_oauth=success").into_response();
+    response
+        .headers_mut()
+        .insert(axum::http::header::SET_COOKIE, session_cookie(&session_id));
+    Ok(response)
 }
 
 async fn x_oauth_status(
     State(state): State<AppState>,
+    headers: HeaderMap,
 ) -> Result<Json<XOAuthStatus>, StatusCode> {
     let enabled = oauth_config().is_some();
-    let connected = get_user_token(&state).await.is_some();
+    let connected = match session_id_from_headers(&headers) {
+        Some(session_id) => get_user_token(&state, &session_id).await.is_some(),
+        None => false,
+    };
     Ok(Json(XOAuthStatus { enabled, connected }))
 }
 
-async fn get_or_create_channel(
-    state: &AppState,
-    request_id: &str,
-) -> broadcast::Sender<StreamEvent> {
+async fn x_oauth_introspect(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Json<XOAuthIntrospection> {
+    let Some(session_id) = session_id_from_headers(&headers) else {
+        return Json(XOAuthIntrospection::disconnected());
+    };
+    let Some(session) = state.token_store.get(&session_id).await else {
+        return Json(XOAuthIntrospection::disconnected());
+    };
+
+    let now = now_ms();
+    let expires_in_seconds = if session.expires_at_ms > now {
+        ((session.expires_at_ms - now) / 1000) as u64
+    } else {
+        0
+    };
+
+    Json(XOAuthIntrospection {
+        connected: true,
+        scope: session
+            .scope
+            .as_deref()
+            .map(|scope| scope.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default(),
+        expires_at_ms: Some(session.expires_at_ms),
+        expires_in_seconds: Some(expires_in_seconds),
+        has_refresh_token: session.refresh_token.is_some(),
+        user_id: session.x_user_id,
+        username: session.x_username,
+    })
+}
+
+async fn x_oauth_logout(State(state): State<AppState>, headers: HeaderMap) -> impl IntoResponse {
+    if let Some(session_id) = session_id_from_headers(&headers) {
+        let session = match state.token_store.remove(&session_id).await {
+            Ok(session) => session,
+            Err(err) => {
+                tracing::warn!(error = %err, "failed to remove X OAuth session");
+                None
+            }
+        };
+        if let Some(session) = session {
+            if let Some(config) = oauth_config() {
+                if let Err(err) = revoke_x_token(&config, &session.access_token).await {
+                    tracing::warn!(error = %err, "failed to revoke X OAuth token");
+                }
+            }
+        }
+    }
+
+    let mut response = StatusCode::NO_CONTENT.into_response();
+    response
+        .headers_mut()
+        .insert(axum::http::header::SET_COOKIE, expired_session_cookie());
+    response
+}
+
+async fn x_oauth_device_start(
+    State(state): State<AppState>,
+) -> Result<Json<XDeviceStartResponse>, (StatusCode, String)> {
+    let config = oauth_config()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, "X OAuth not configured".to_string()))?;
+
+    let mut params = vec![
+        ("client_id", config.client_id.as_str()),
+        ("scope", config.scope.as_str()),
+    ];
+    if matches!(config.auth_mode, OAuthAuthMode::Body) {
+        params.push(("client_secret", config.client_secret.as_str()));
+    }
+
+    let mut request = reqwest::Client::new().post(&config.device_auth_url);
+    if matches!(config.auth_mode, OAuthAuthMode::Basic) {
+        request = request.basic_auth(&config.client_id, Some(&config.client_secret));
+    }
+
+    let response = request
+        .form(&params)
+        .send()
+        .await
+        .map_err(|err| (StatusCode::BAD_GATEWAY, format!("X OAuth device request failed: {}", err)))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_body = response.text().await.unwrap_or_default();
+        return Err((
+            StatusCode::BAD_GATEWAY,
+            format!("X OAuth device error: {} {}", status, error_body.trim()),
+        ));
+    }
+
+    let body: DeviceAuthorizationResponse = response
+        .json()
+        .await
+        .map_err(|err| (StatusCode::BAD_GATEWAY, format!("X OAuth device parse failed: {}", err)))?;
+
+    let interval = body.interval.unwrap_or(5);
+    let verification_uri = body
+        .verification_uri
+        .or(body.verification_url)
+        .unwrap_or_default();
+
+    {
+        let mut guard = state.device_codes.lock().await;
+        guard.insert(
+            body.device_code.clone(),
+            DevicePendingAuth {
+                interval,
+                next_poll_at: Instant::now() + Duration::from_secs(interval),
+                expires_at_ms: now_ms() + (body.expires_in as u128) * 1000,
+            },
+        );
+    }
+    schedule_device_code_cleanup(
+        state.device_codes.clone(),
+        body.device_code.clone(),
+        body.expires_in,
+    );
+
+    Ok(Json(XDeviceStartResponse {
+        device_code: body.device_code,
+        user_code: body.user_code,
+        verification_uri,
+        interval,
+        expires_in: body.expires_in,
+    }))
+}
+
+async fn x_oauth_device_poll(
+    State(state): State<AppState>,
+    Query(query): Query<XDevicePollQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let config = oauth_config()
+        .ok_or_else(|| (StatusCode::BAD_REQUEST, "X OAuth not configured".to_string()))?;
+
+    let next_poll_at = {
+        let mut guard = state.device_codes.lock().await;
+        match guard.get(&query.device_code) {
+            Some(pending) if now_ms() > pending.expires_at_ms => {
+                guard.remove(&query.device_code);
+                None
+            }
+            Some(pending) => Some(pending.next_poll_at),
+            None => None,
+        }
+    }
+    .ok_or_else(|| (StatusCode::BAD_REQUEST, "Unknown or expired device_code".to_string()))?;
+
+    if Instant::now() < next_poll_at {
+        return Ok(Json(XDevicePollResponse::AuthorizationPending).into_response());
+    }
+
+    match poll_device_token(&config, &query.device_code).await {
+        Ok(DeviceTokenPoll::Token(mut token)) => {
+            {
+                let mut guard = state.device_codes.lock().await;
+                guard.remove(&query.device_code);
+            }
+            if let Some(client) = state.x_client.as_ref() {
+                match client.fetch_me_with_token(&token.access_token).await {
+                    Ok(profile) => {
+                        token.x_user_id = Some(profile.id);
+                        token.x_username = Some(profile.username);
+                    }
+                    Err(err) => {
+                        tracing::warn!(error = %err, "failed to fetch X profile for new OAuth session");
+                    }
+                }
+            }
+            let session_id = random_token(24);
+            state
+                .token_store
+                .insert(session_id.clone(), token)
+                .await
+                .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))?;
+            let mut response = Json(XDevicePollResponse::Complete).into_response();
+            response
+                .headers_mut()
+                .insert(axum::http::header::SET_COOKIE, session_cookie(&session_id));
+            Ok(response)
+        }
+        Ok(DeviceTokenPoll::Pending) => {
+            let mut guard = state.device_codes.lock().await;
+            if let Some(pending) = guard.get_mut(&query.device_code) {
+                pending.next_poll_at = Instant::now() + Duration::from_secs(pending.interval);
+            }
+            Ok(Json(XDevicePollResponse::AuthorizationPending).into_response())
+        }
+        Ok(DeviceTokenPoll::SlowDown) => {
+            let mut guard = state.device_codes.lock().await;
+            if let Some(pending) = guard.get_mut(&query.device_code) {
+                pending.interval += 5;
+                pending.next_poll_at = Instant::now() + Duration::from_secs(pending.interval);
+            }
+            Ok(Json(XDevicePollResponse::SlowDown).into_response())
+        }
+        Err(err) => {
+            let mut guard = state.device_codes.lock().await;
+            guard.remove(&query.device_code);
+            Err((StatusCode::BAD_GATEWAY, err))
+        }
+    }
+}
+
+async fn get_or_create_channel(state: &AppState, request_id: &str) -> Channel {
     let mut guard = state.channels.lock().await;
-    if let Some(sender) = guard.get(request_id) {
-        return sender.clone();
+    if let Some(channel) = guard.get(request_id) {
+        return channel.clone();
     }
     let (sender, _) = broadcast::channel(256);
-    guard.insert(request_id.to_string(), sender.clone());
-    sender
+    let channel = Channel {
+        sender,
+        buffer: Arc::new(std::sync::Mutex::new(VecDeque::with_capacity(STREAM_REPLAY_BUFFER))),
+        next_seq: Arc::new(AtomicU64::new(0)),
+    };
+    guard.insert(request_id.to_string(), channel.clone());
+    channel
 }
 
-fn send_event(sender: &broadcast::Sender<StreamEvent>, event: &str, message: &str) {
-    let _ = sender.send(StreamEvent {
+fn send_event(channel: &Channel, event: &str, message: &str) {
+    let event = StreamEvent {
         event: event.to_string(),
         message: message.to_string(),
         timestamp_ms: now_ms(),
-    });
+        seq: channel.next_seq.fetch_add(1, Ordering::Relaxed),
+    };
+
+    {
+        let mut buffer = channel.buffer.lock().unwrap_or_else(|err| err.into_inner());
+        if buffer.len() >= STREAM_REPLAY_BUFFER {
+            buffer.pop_front();
+        }
+        buffer.push_back(event.clone());
+    }
+
+    let _ = channel.sender.send(event);
 }
 
-fn schedule_cleanup(channels: Arc<Mutex<HashMap<String, broadcast::Sender<StreamEvent>>>>, request_id: String) {
+fn schedule_cleanup(channels: Arc<Mutex<HashMap<String, Channel>>>, request_id: String) {
     tokio::spawn(async move {
         tokio::time::sleep(Duration::from_secs(10)).await;
         let mut guard = channels.lock().await;
@@ -915,13 +1904,43 @@ fn schedule_cleanup(channels: Arc<Mutex<HashMap<String, broadcast::Sender<Stream
     });
 }
 
-fn start_progress(sender: broadcast::Sender<StreamEvent>) -> Arc<AtomicBool> {
+/// Evicts an abandoned `x_oauth_start` state/verifier pair once
+/// `OAUTH_STATE_TTL_MS` has passed, so a flow the user never completes
+/// doesn't linger in memory forever.
+fn schedule_oauth_state_cleanup(
+    oauth_state: Arc<Mutex<HashMap<String, PendingOAuthState>>>,
+    state_value: String,
+) {
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(OAUTH_STATE_TTL_MS as u64)).await;
+        let mut guard = oauth_state.lock().await;
+        guard.remove(&state_value);
+    });
+}
+
+/// Evicts an abandoned `x_oauth_device_start` device code once the
+/// authorization server's own `expires_in` has passed, so a device flow the
+/// user never completes (never visits `verification_uri`) doesn't linger in
+/// `device_codes` forever -- mirrors `schedule_oauth_state_cleanup`.
+fn schedule_device_code_cleanup(
+    device_codes: Arc<Mutex<HashMap<String, DevicePendingAuth>>>,
+    device_code: String,
+    expires_in: u64,
+) {
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(expires_in)).await;
+        let mut guard = device_codes.lock().await;
+        guard.remove(&device_code);
+    });
+}
+
+fn start_progress(channel: Channel) -> Arc<AtomicBool> {
     let done = Arc::new(AtomicBool::new(false));
     let done_flag = done.clone();
     tokio::spawn(async move {
         let mut elapsed = 0;
         while !done_flag.load(Ordering::Relaxed) {
-            send_event(&sender, "progress", &format!("Waiting on Grok... {}s", elapsed));
+            send_event(&channel, "progress", &format!("Waiting on AI model... {}s", elapsed));
             elapsed += 1;
             tokio::time::sleep(Duration::from_secs(1)).await;
         }
@@ -948,6 +1967,8 @@ struct OAuthConfig {
     redirect_uri: String,
     scope: String,
     token_url: String,
+    revoke_url: String,
+    device_auth_url: String,
     auth_mode: OAuthAuthMode,
 }
 
@@ -956,9 +1977,17 @@ fn oauth_config() -> Option<OAuthConfig> {
     let client_secret = std::env::var("X_OAUTH_CLIENT_SECRET").ok()?;
     let redirect_uri = std::env::var("X_OAUTH_REDIRECT_URI")
         .unwrap_or_else(|_| "http://localhost:8787/api/x/oauth/callback".to_string());
-    let scope = std::env::var("X_OAUTH_SCOPE").unwrap_or_else(|_| "users.read".to_string());
+    // `offline.access` is what gets X to hand back a `refresh_token` at all;
+    // without it `get_user_token` has nothing to refresh with once the
+    // short-lived access token expires.
+    let scope = std::env::var("X_OAUTH_SCOPE")
+        .unwrap_or_else(|_| "users.read offline.access".to_string());
     let token_url = std::env::var("X_OAUTH_TOKEN_URL")
         .unwrap_or_else(|_| "https://api.twitter.com/2/oauth2/token".to_string());
+    let revoke_url = std::env::var("X_OAUTH_REVOKE_URL")
+        .unwrap_or_else(|_| "https://api.twitter.com/2/oauth2/revoke".to_string());
+    let device_auth_url = std::env::var("X_OAUTH_DEVICE_URL")
+        .unwrap_or_else(|_| "https://api.twitter.com/2/oauth2/device/code".to_string());
     let auth_mode = match std::env::var("X_OAUTH_AUTH_MODE")
         .unwrap_or_else(|_| "basic".to_string())
         .to_lowercase()
@@ -973,6 +2002,8 @@ fn oauth_config() -> Option<OAuthConfig> {
         redirect_uri,
         scope,
         token_url,
+        revoke_url,
+        device_auth_url,
         auth_mode,
     })
 }
@@ -1023,29 +2054,218 @@ async fn exchange_oauth_code(
         .map_err(|err| format!("X OAuth token parse failed: {}", err))?;
 
     let expires_in = body.expires_in.unwrap_or(3600);
-    let expires_at = Instant::now() + Duration::from_secs(expires_in.saturating_sub(30));
+    let expires_at_ms = now_ms() + Duration::from_secs(expires_in.saturating_sub(30)).as_millis();
+
+    Ok(XUserToken {
+        access_token: body.access_token,
+        refresh_token: body.refresh_token,
+        expires_at_ms,
+        scope: body.scope,
+        x_user_id: None,
+        x_username: None,
+    })
+}
+
+async fn refresh_x_token(config: &OAuthConfig, refresh_token: &str) -> Result<XUserToken, String> {
+    let mut params = vec![
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token),
+        ("client_id", config.client_id.as_str()),
+    ];
+    if matches!(config.auth_mode, OAuthAuthMode::Body) {
+        params.push(("client_secret", config.client_secret.as_str()));
+    }
+
+    let mut request = reqwest::Client::new().post(&config.token_url);
+    if matches!(config.auth_mode, OAuthAuthMode::Basic) {
+        request = request.basic_auth(&config.client_id, Some(&config.client_secret));
+    }
+
+    let response = request
+        .form(&params)
+        .send()
+        .await
+        .map_err(|err| format!("X OAuth refresh request failed: {}", err))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let error_body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| String::new());
+        let detail = error_body.trim();
+        if detail.is_empty() {
+            return Err(format!("X OAuth refresh error: {}", status));
+        }
+        return Err(format!("X OAuth refresh error: {} {}", status, detail));
+    }
+
+    let body: OAuthTokenResponse = response
+        .json()
+        .await
+        .map_err(|err| format!("X OAuth refresh parse failed: {}", err))?;
+
+    let expires_in = body.expires_in.unwrap_or(3600);
+    let expires_at_ms = now_ms() + Duration::from_secs(expires_in.saturating_sub(30)).as_millis();
 
     Ok(XUserToken {
         access_token: body.access_token,
-        expires_at,
+        // X rotates refresh tokens but doesn't always echo one back; fall
+        // back to the one we just spent so the next refresh can still fire.
+        refresh_token: body.refresh_token.or_else(|| Some(refresh_token.to_string())),
+        expires_at_ms,
+        scope: body.scope,
+        // The refresh response doesn't repeat the cached user identity;
+        // callers should carry `x_user_id`/`x_username` over from the
+        // session being refreshed.
+        x_user_id: None,
+        x_username: None,
     })
 }
 
-async fn get_user_token(state: &AppState) -> Option<String> {
-    let mut guard = state.x_user_token.lock().await;
-    if let Some(token) = guard.as_ref() {
-        if Instant::now() < token.expires_at {
-            return Some(token.access_token.clone());
+async fn revoke_x_token(config: &OAuthConfig, token: &str) -> Result<(), String> {
+    let params = [("token", token), ("client_id", config.client_id.as_str())];
+
+    let mut request = reqwest::Client::new().post(&config.revoke_url);
+    if matches!(config.auth_mode, OAuthAuthMode::Basic) {
+        request = request.basic_auth(&config.client_id, Some(&config.client_secret));
+    }
+
+    let response = request
+        .form(&params)
+        .send()
+        .await
+        .map_err(|err| format!("X OAuth revoke request failed: {}", err))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("X OAuth revoke error: {}", status));
+    }
+    Ok(())
+}
+
+enum DeviceTokenPoll {
+    Token(XUserToken),
+    Pending,
+    SlowDown,
+}
+
+async fn poll_device_token(config: &OAuthConfig, device_code: &str) -> Result<DeviceTokenPoll, String> {
+    let mut params = vec![
+        ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+        ("device_code", device_code),
+        ("client_id", config.client_id.as_str()),
+    ];
+    if matches!(config.auth_mode, OAuthAuthMode::Body) {
+        params.push(("client_secret", config.client_secret.as_str()));
+    }
+
+    let mut request = reqwest::Client::new().post(&config.token_url);
+    if matches!(config.auth_mode, OAuthAuthMode::Basic) {
+        request = request.basic_auth(&config.client_id, Some(&config.client_secret));
+    }
+
+    let response = request
+        .form(&params)
+        .send()
+        .await
+        .map_err(|err| format!("X OAuth device poll request failed: {}", err))?;
+
+    let status = response.status();
+    let body_text = response
+        .text()
+        .await
+        .map_err(|err| format!("X OAuth device poll read failed: {}", err))?;
+
+    if status.is_success() {
+        let body: OAuthTokenResponse = serde_json::from_str(&body_text)
+            .map_err(|err| format!("X OAuth device poll parse failed: {}", err))?;
+        let expires_in = body.expires_in.unwrap_or(3600);
+        let expires_at_ms = now_ms() + Duration::from_secs(expires_in.saturating_sub(30)).as_millis();
+        return Ok(DeviceTokenPoll::Token(XUserToken {
+            access_token: body.access_token,
+            refresh_token: body.refresh_token,
+            expires_at_ms,
+            scope: body.scope,
+            x_user_id: None,
+            x_username: None,
+        }));
+    }
+
+    let error: OAuthErrorResponse = serde_json::from_str(&body_text)
+        .map_err(|_| format!("X OAuth device poll error: {}", status))?;
+    match error.error.as_str() {
+        "authorization_pending" => Ok(DeviceTokenPoll::Pending),
+        "slow_down" => Ok(DeviceTokenPoll::SlowDown),
+        other => Err(error.error_description.unwrap_or_else(|| other.to_string())),
+    }
+}
+
+/// Look up the session's token, transparently refreshing it first when
+/// `expires_at_ms` is within `X_TOKEN_REFRESH_SKEW_MS`. Drops the session if
+/// it has no usable token left.
+async fn get_user_token(state: &AppState, session_id: &str) -> Option<String> {
+    let session = state.token_store.get(session_id).await?;
+
+    if now_ms() + X_TOKEN_REFRESH_SKEW_MS < session.expires_at_ms {
+        return Some(session.access_token);
+    }
+
+    let Some(refresh_token) = session.refresh_token.clone() else {
+        let _ = state.token_store.remove(session_id).await;
+        return None;
+    };
+
+    let config = oauth_config()?;
+    match refresh_x_token(&config, &refresh_token).await {
+        Ok(mut refreshed) => {
+            refreshed.scope = refreshed.scope.or(session.scope);
+            refreshed.x_user_id = session.x_user_id;
+            refreshed.x_username = session.x_username;
+            let access_token = refreshed.access_token.clone();
+            if let Err(err) = state.token_store.insert(session_id.to_string(), refreshed).await {
+                tracing::warn!(error = %err, "failed to persist refreshed X OAuth token");
+            }
+            Some(access_token)
+        }
+        Err(err) => {
+            tracing::warn!(error = %err, "failed to refresh X OAuth token");
+            let _ = state.token_store.remove(session_id).await;
+            None
         }
     }
-    guard.take();
-    None
+}
+
+fn session_id_from_headers(headers: &HeaderMap) -> Option<String> {
+    let cookie_header = headers.get(axum::http::header::COOKIE)?.to_str().ok()?;
+    cookie_header.split(';').find_map(|pair| {
+        let (name, value) = pair.trim().split_once('=')?;
+        (name == X_SESSION_COOKIE).then(|| value.to_string())
+    })
+}
+
+fn session_cookie(session_id: &str) -> HeaderValue {
+    HeaderValue::from_str(&format!(
+        "{}={}; Path=/; HttpOnly; Secure; SameSite=Lax; Max-Age=2592000",
+        X_SESSION_COOKIE, session_id
+    ))
+    .unwrap_or_else(|_| HeaderValue::from_static(""))
+}
+
+fn expired_session_cookie() -> HeaderValue {
+    HeaderValue::from_str(&format!(
+        "{}=; Path=/; HttpOnly; Secure; SameSite=Lax; Max-Age=0",
+        X_SESSION_COOKIE
+    ))
+    .unwrap_or_else(|_| HeaderValue::from_static(""))
 }
 
 #[derive(Deserialize)]
 struct OAuthTokenResponse {
     access_token: String,
     expires_in: Option<u64>,
+    refresh_token: Option<String>,
+    scope: Option<String>,
 }
 
 fn generate_request_id() -> String {
@@ -1072,6 +2292,23 @@ fn snapshot_path() -> PathBuf {
     PathBuf::from("data").join("snapshots.json")
 }
 
+/// How many snapshots `SnapshotStore` keeps before evicting the oldest.
+/// `SIM_SNAPSHOT_RETENTION=0` disables eviction entirely; unset falls back to
+/// the store's own default.
+fn snapshot_retention() -> Option<usize> {
+    match std::env::var("SIM_SNAPSHOT_RETENTION") {
+        Ok(value) => match value.parse::<usize>() {
+            Ok(0) => None,
+            Ok(limit) => Some(limit),
+            Err(_) => {
+                tracing::warn!(value = %value, "invalid SIM_SNAPSHOT_RETENTION, ignoring");
+                None
+            }
+        },
+        Err(_) => Some(crate::snapshots::DEFAULT_RETENTION),
+    }
+}
+
 fn user_profiles_path() -> PathBuf {
     if let Ok(path) = std::env::var("USER_PROFILES_PATH") {
         return PathBuf::from(path);
@@ -1079,6 +2316,20 @@ fn user_profiles_path() -> PathBuf {
     PathBuf::from("data").join("user_profiles.json")
 }
 
+fn trace_store_path() -> PathBuf {
+    if let Ok(path) = std::env::var("TRACE_DB_PATH") {
+        return PathBuf::from(path);
+    }
+    PathBuf::from("data").join("traces.db")
+}
+
+fn token_store_path() -> PathBuf {
+    if let Ok(path) = std::env::var("TOKEN_STORE_PATH") {
+        return PathBuf::from(path);
+    }
+    PathBuf::from("data").join("x_tokens.json")
+}
+
 fn chrono_like_timestamp() -> String {
     now_ms().to_string()
 }
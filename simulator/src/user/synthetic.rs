@@ -1,6 +1,6 @@
 use rand::{rngs::StdRng, Rng, SeedableRng};
-use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::ids::Timestamp;
 use crate::user::profile::{ActionFlags, EngagementEvent, UserProfile};
 
 const DEFAULT_HISTORY_LEN: usize = 50;
@@ -10,7 +10,7 @@ pub fn generate_synthetic_history(profile: &UserProfile, seed: u64) -> Vec<Engag
     let engagement_rate = estimate_engagement_rate(profile);
 
     let mut history = Vec::new();
-    let now = current_timestamp();
+    let now = Timestamp::now().epoch_seconds();
 
     for idx in 0..DEFAULT_HISTORY_LEN {
         if rng.gen::<f64>() >= engagement_rate {
@@ -19,9 +19,13 @@ pub fn generate_synthetic_history(profile: &UserProfile, seed: u64) -> Vec<Engag
 
         let actions = sample_actions(&mut rng);
         history.push(EngagementEvent {
-            post_id: format!("synthetic_{}", idx),
-            author_id: format!("author_{}", rng.gen_range(0..200)),
-            timestamp: now - (idx as i64 * 3600),
+            post_id: format!("synthetic_{}", idx)
+                .try_into()
+                .expect("synthetic post id is always non-empty"),
+            author_id: format!("author_{}", rng.gen_range(0..200))
+                .try_into()
+                .expect("synthetic author id is always non-empty"),
+            timestamp: Timestamp::from_epoch_seconds(now - (idx as i64 * 3600)),
             actions,
         });
     }
@@ -62,10 +66,3 @@ fn sample_actions(rng: &mut StdRng) -> ActionFlags {
         reported,
     }
 }
-
-fn current_timestamp() -> i64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|duration| duration.as_secs() as i64)
-        .unwrap_or(0)
-}
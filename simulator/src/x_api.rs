@@ -1,8 +1,15 @@
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
 use reqwest::header::AUTHORIZATION;
 use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use std::collections::HashMap;
 use std::env;
+use std::io::{self, Write};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::Mutex;
 
 #[derive(Clone)]
@@ -10,6 +17,7 @@ pub struct XApiClient {
     client: reqwest::Client,
     api_base: String,
     auth: XApiAuth,
+    rate_limiter: Arc<RateLimiter>,
 }
 
 #[derive(Clone)]
@@ -24,6 +32,23 @@ enum XApiAuth {
         client_type: Option<String>,
         token_cache: Arc<Mutex<Option<OAuthTokenCache>>>,
     },
+    OAuth1UserContext(OAuth1Credentials),
+}
+
+#[derive(Clone)]
+struct OAuth1Credentials {
+    consumer_key: String,
+    consumer_secret: String,
+    token: String,
+    token_secret: String,
+}
+
+/// A temporary, unauthorized token pair returned by the request-token step of
+/// the OAuth 1.0a handshake, exchanged for an access token once the user has
+/// authorized it and supplied the PIN.
+struct OAuth1RequestToken {
+    token: String,
+    secret: String,
 }
 
 #[derive(Clone)]
@@ -38,6 +63,72 @@ enum OAuthAuthMode {
     Body,
 }
 
+/// Token-bucket rate limiting keyed by endpoint, fed by the
+/// `x-rate-limit-remaining`/`x-rate-limit-reset` headers X returns on every
+/// response. Callers pause until the window resets once a bucket is known to
+/// be exhausted, so a burst of calls backs off on its own instead of
+/// tripping X's per-window limit and coming back as an opaque 429.
+#[derive(Default)]
+struct RateLimiter {
+    buckets: Mutex<HashMap<String, RateLimitState>>,
+}
+
+#[derive(Clone, Copy)]
+struct RateLimitState {
+    remaining: u32,
+    reset_at: Instant,
+}
+
+impl RateLimiter {
+    fn new() -> Self {
+        Self {
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns how long a caller should wait before hitting `endpoint`, if
+    /// the last known window for it reported zero calls remaining.
+    async fn wait_for(&self, endpoint: &str) -> Option<Duration> {
+        let guard = self.buckets.lock().await;
+        let state = guard.get(endpoint)?;
+        if state.remaining == 0 {
+            let now = Instant::now();
+            if now < state.reset_at {
+                return Some(state.reset_at - now);
+            }
+        }
+        None
+    }
+
+    /// Updates the bucket for `endpoint` from a response's rate-limit
+    /// headers. Responses missing either header leave the bucket untouched.
+    async fn record(&self, endpoint: &str, headers: &reqwest::header::HeaderMap) {
+        let remaining = headers
+            .get("x-rate-limit-remaining")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u32>().ok());
+        let reset_unix = headers
+            .get("x-rate-limit-reset")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+        let (Some(remaining), Some(reset_unix)) = (remaining, reset_unix) else {
+            return;
+        };
+
+        let now_unix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let reset_at = Instant::now() + Duration::from_secs(reset_unix.saturating_sub(now_unix));
+
+        let mut guard = self.buckets.lock().await;
+        guard.insert(
+            endpoint.to_string(),
+            RateLimitState { remaining, reset_at },
+        );
+    }
+}
+
 impl XApiClient {
     pub fn from_env() -> Option<Self> {
         let api_base = env::var("X_API_BASE").unwrap_or_else(|_| "https://api.twitter.com/2".to_string());
@@ -48,6 +139,7 @@ impl XApiClient {
                 client,
                 api_base,
                 auth: XApiAuth::Bearer(decode_bearer(bearer_token)),
+                rate_limiter: Arc::new(RateLimiter::new()),
             });
         }
 
@@ -81,15 +173,168 @@ impl XApiClient {
                     client_type,
                     token_cache: Arc::new(Mutex::new(None)),
                 },
+                rate_limiter: Arc::new(RateLimiter::new()),
             });
         }
 
+        if let (Ok(consumer_key), Ok(consumer_secret), Ok(token), Ok(token_secret)) = (
+            env::var("X_OAUTH1_CONSUMER_KEY"),
+            env::var("X_OAUTH1_CONSUMER_SECRET"),
+            env::var("X_OAUTH1_ACCESS_TOKEN"),
+            env::var("X_OAUTH1_ACCESS_TOKEN_SECRET"),
+        ) {
+            return Some(Self::from_oauth1_user_context(
+                consumer_key,
+                consumer_secret,
+                token,
+                token_secret,
+                api_base,
+            ));
+        }
+
         None
     }
 
+    /// Builds a client that signs every request with a previously-acquired
+    /// OAuth 1.0a user token/secret pair, so calls like `fetch_me` act on the
+    /// authenticated user's behalf instead of the app-only context.
+    pub fn from_oauth1_user_context(
+        consumer_key: String,
+        consumer_secret: String,
+        token: String,
+        token_secret: String,
+        api_base: String,
+    ) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_base,
+            auth: XApiAuth::OAuth1UserContext(OAuth1Credentials {
+                consumer_key,
+                consumer_secret,
+                token,
+                token_secret,
+            }),
+            rate_limiter: Arc::new(RateLimiter::new()),
+        }
+    }
+
     pub async fn fetch_user_by_username(&self, username: &str) -> Result<XUserProfile, String> {
-        let token = self.bearer_token().await?;
-        self.fetch_user_by_username_with_token(username, &token).await
+        self.fetch_user_by_username_on(username, &mut |_| {}).await
+    }
+
+    /// Same as `fetch_user_by_username`, but reports rate-limit backoff
+    /// through `on_rate_limited` instead of silently sleeping, so a caller
+    /// with a streaming channel can surface it to the UI.
+    pub async fn fetch_user_by_username_on(
+        &self,
+        username: &str,
+        on_rate_limited: &mut dyn FnMut(&str),
+    ) -> Result<XUserProfile, String> {
+        let endpoint = "users/by/username";
+        self.await_rate_limit(endpoint, on_rate_limited).await;
+
+        let url = format!(
+            "{}/users/by/username/{}",
+            self.api_base.trim_end_matches('/'),
+            username
+        );
+        let query = [("user.fields", "public_metrics,created_at,verified,protected")];
+        let auth_header = self.auth_header("GET", &url, &query).await?;
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&query)
+            .header(AUTHORIZATION, auth_header)
+            .send()
+            .await
+            .map_err(|err| format!("X API request failed: {}", err))?;
+
+        let status = response.status();
+        self.rate_limiter.record(endpoint, response.headers()).await;
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            on_rate_limited(&format!("rate limited on {}", endpoint));
+        }
+        if !status.is_success() {
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| String::new());
+            let detail = error_body.trim();
+            if detail.is_empty() {
+                return Err(format!("X API error: {}", status));
+            }
+            return Err(format!("X API error: {} {}", status, detail));
+        }
+
+        let body: XUserResponse = response
+            .json()
+            .await
+            .map_err(|err| format!("X API response parse failed: {}", err))?;
+
+        let user = body
+            .data
+            .ok_or_else(|| "X API response missing user data".to_string())?;
+
+        Ok(XUserProfile::from(user))
+    }
+
+    /// Fetches the authenticated user's own profile, signing the request with
+    /// whatever auth context this client was built with (OAuth 1.0a user
+    /// tokens included).
+    pub async fn fetch_me(&self) -> Result<XUserProfile, String> {
+        self.fetch_me_on(&mut |_| {}).await
+    }
+
+    /// Same as `fetch_me`, but reports rate-limit backoff through
+    /// `on_rate_limited` instead of silently sleeping.
+    pub async fn fetch_me_on(
+        &self,
+        on_rate_limited: &mut dyn FnMut(&str),
+    ) -> Result<XUserProfile, String> {
+        let endpoint = "users/me";
+        self.await_rate_limit(endpoint, on_rate_limited).await;
+
+        let url = format!("{}/users/me", self.api_base.trim_end_matches('/'));
+        let query = [("user.fields", "public_metrics,created_at,verified,protected")];
+        let auth_header = self.auth_header("GET", &url, &query).await?;
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&query)
+            .header(AUTHORIZATION, auth_header)
+            .send()
+            .await
+            .map_err(|err| format!("X API request failed: {}", err))?;
+
+        let status = response.status();
+        self.rate_limiter.record(endpoint, response.headers()).await;
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            on_rate_limited(&format!("rate limited on {}", endpoint));
+        }
+        if !status.is_success() {
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| String::new());
+            let detail = error_body.trim();
+            if detail.is_empty() {
+                return Err(format!("X API error: {}", status));
+            }
+            return Err(format!("X API error: {} {}", status, detail));
+        }
+
+        let body: XUserResponse = response
+            .json()
+            .await
+            .map_err(|err| format!("X API response parse failed: {}", err))?;
+
+        let user = body
+            .data
+            .ok_or_else(|| "X API response missing user data".to_string())?;
+
+        Ok(XUserProfile::from(user))
     }
 
     pub async fn fetch_user_by_username_with_token(
@@ -97,6 +342,21 @@ impl XApiClient {
         username: &str,
         token: &str,
     ) -> Result<XUserProfile, String> {
+        self.fetch_user_by_username_with_token_on(username, token, &mut |_| {})
+            .await
+    }
+
+    /// Same as `fetch_user_by_username_with_token`, but reports rate-limit
+    /// backoff through `on_rate_limited` instead of silently sleeping.
+    pub async fn fetch_user_by_username_with_token_on(
+        &self,
+        username: &str,
+        token: &str,
+        on_rate_limited: &mut dyn FnMut(&str),
+    ) -> Result<XUserProfile, String> {
+        let endpoint = "users/by/username";
+        self.await_rate_limit(endpoint, on_rate_limited).await;
+
         let response = self
             .client
             .get(format!(
@@ -111,6 +371,10 @@ impl XApiClient {
             .map_err(|err| format!("X API request failed: {}", err))?;
 
         let status = response.status();
+        self.rate_limiter.record(endpoint, response.headers()).await;
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            on_rate_limited(&format!("rate limited on {}", endpoint));
+        }
         if !status.is_success() {
             let error_body = response
                 .text()
@@ -136,6 +400,19 @@ impl XApiClient {
     }
 
     pub async fn fetch_me_with_token(&self, token: &str) -> Result<XUserProfile, String> {
+        self.fetch_me_with_token_on(token, &mut |_| {}).await
+    }
+
+    /// Same as `fetch_me_with_token`, but reports rate-limit backoff through
+    /// `on_rate_limited` instead of silently sleeping.
+    pub async fn fetch_me_with_token_on(
+        &self,
+        token: &str,
+        on_rate_limited: &mut dyn FnMut(&str),
+    ) -> Result<XUserProfile, String> {
+        let endpoint = "users/me";
+        self.await_rate_limit(endpoint, on_rate_limited).await;
+
         let response = self
             .client
             .get(format!(
@@ -149,6 +426,10 @@ impl XApiClient {
             .map_err(|err| format!("X API request failed: {}", err))?;
 
         let status = response.status();
+        self.rate_limiter.record(endpoint, response.headers()).await;
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            on_rate_limited(&format!("rate limited on {}", endpoint));
+        }
         if !status.is_success() {
             let error_body = response
                 .text()
@@ -173,9 +454,280 @@ impl XApiClient {
         Ok(XUserProfile::from(user))
     }
 
+    /// Fetches a single tweet's `public_metrics` (likes, replies, reposts,
+    /// quotes, impressions), for backtesting simulator predictions against
+    /// real outcomes.
+    pub async fn fetch_tweet_metrics(&self, post_id: &str) -> Result<TweetMetrics, String> {
+        let endpoint = "tweets";
+        self.await_rate_limit(endpoint, &mut |_| {}).await;
+
+        let token = self.bearer_token().await?;
+        let response = self
+            .client
+            .get(format!(
+                "{}/tweets/{}",
+                self.api_base.trim_end_matches('/'),
+                post_id
+            ))
+            .query(&[("tweet.fields", "public_metrics")])
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .send()
+            .await
+            .map_err(|err| format!("X API request failed: {}", err))?;
+
+        let status = response.status();
+        self.rate_limiter.record(endpoint, response.headers()).await;
+        if !status.is_success() {
+            let error_body = response
+                .text()
+                .await
+                .unwrap_or_else(|_| String::new());
+            let detail = error_body.trim();
+            if detail.is_empty() {
+                return Err(format!("X API error: {}", status));
+            }
+            return Err(format!("X API error: {} {}", status, detail));
+        }
+
+        let body: TweetResponse = response
+            .json()
+            .await
+            .map_err(|err| format!("X API response parse failed: {}", err))?;
+
+        body.data
+            .and_then(|tweet| tweet.public_metrics)
+            .ok_or_else(|| "X API response missing public_metrics".to_string())
+    }
+
+    /// Sleeps out any known backoff window for `endpoint`, reporting it
+    /// through `on_rate_limited` first so a streaming caller can tell the UI
+    /// why the request is paused instead of it just looking stalled.
+    async fn await_rate_limit(&self, endpoint: &str, on_rate_limited: &mut dyn FnMut(&str)) {
+        if let Some(wait) = self.rate_limiter.wait_for(endpoint).await {
+            on_rate_limited(&format!(
+                "rate limit reached for {}; waiting {}s",
+                endpoint,
+                wait.as_secs()
+            ));
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    pub(crate) fn http_client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    pub(crate) fn api_base(&self) -> &str {
+        &self.api_base
+    }
+
+    pub(crate) async fn bearer_token_for_calibration(&self) -> Result<String, String> {
+        self.bearer_token().await
+    }
+
+    /// Resolves the `Authorization` header value for a request, signing it
+    /// with OAuth 1.0a when the client holds user-context credentials and
+    /// falling back to the existing bearer-token flow otherwise.
+    async fn auth_header(
+        &self,
+        method: &str,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<String, String> {
+        match &self.auth {
+            XApiAuth::OAuth1UserContext(creds) => {
+                let params: Vec<(String, String)> = query
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect();
+                Ok(oauth1_authorization_header(
+                    method,
+                    url,
+                    &creds.consumer_key,
+                    &creds.consumer_secret,
+                    Some(&creds.token),
+                    Some(&creds.token_secret),
+                    &params,
+                    &[],
+                ))
+            }
+            _ => {
+                let token = self.bearer_token().await?;
+                Ok(format!("Bearer {}", token))
+            }
+        }
+    }
+
+    /// Runs the OAuth 1.0a three-legged (PIN-based) handshake end to end: it
+    /// requests a temporary token, prints the authorization URL for the user
+    /// to open, reads the resulting PIN from stdin, and exchanges it for a
+    /// long-lived user token/secret pair. Intended for one-off CLI setup; the
+    /// returned client can be reused (or its credentials persisted) for
+    /// subsequent `fetch_me`/`fetch_user_by_username` calls.
+    pub async fn authorize_interactive(
+        consumer_key: String,
+        consumer_secret: String,
+        api_base: String,
+    ) -> Result<Self, String> {
+        let request_token_url = env::var("X_OAUTH1_REQUEST_TOKEN_URL")
+            .unwrap_or_else(|_| "https://api.twitter.com/oauth/request_token".to_string());
+        let authorize_url = env::var("X_OAUTH1_AUTHORIZE_URL")
+            .unwrap_or_else(|_| "https://api.twitter.com/oauth/authorize".to_string());
+        let access_token_url = env::var("X_OAUTH1_ACCESS_TOKEN_URL")
+            .unwrap_or_else(|_| "https://api.twitter.com/oauth/access_token".to_string());
+
+        let request_token =
+            Self::oauth1_request_token(&consumer_key, &consumer_secret, &request_token_url)
+                .await?;
+
+        println!(
+            "Open this URL, authorize the app, and enter the PIN it shows:\n{}",
+            Self::oauth1_authorize_url(&authorize_url, &request_token.token)
+        );
+        print!("PIN: ");
+        io::stdout().flush().map_err(|err| format!("failed to flush stdout: {}", err))?;
+        let mut verifier = String::new();
+        io::stdin()
+            .read_line(&mut verifier)
+            .map_err(|err| format!("failed to read PIN from stdin: {}", err))?;
+        let verifier = verifier.trim();
+
+        let (token, token_secret) = Self::oauth1_access_token(
+            &consumer_key,
+            &consumer_secret,
+            &request_token.token,
+            &request_token.secret,
+            verifier,
+            &access_token_url,
+        )
+        .await?;
+
+        Ok(Self::from_oauth1_user_context(
+            consumer_key,
+            consumer_secret,
+            token,
+            token_secret,
+            api_base,
+        ))
+    }
+
+    /// Requests a temporary (unauthorized) OAuth 1.0a token, the first leg of
+    /// the three-legged handshake. Uses the out-of-band callback since this
+    /// crate has no redirect endpoint of its own.
+    async fn oauth1_request_token(
+        consumer_key: &str,
+        consumer_secret: &str,
+        request_token_url: &str,
+    ) -> Result<OAuth1RequestToken, String> {
+        let client = reqwest::Client::new();
+        let auth_header = oauth1_authorization_header(
+            "POST",
+            request_token_url,
+            consumer_key,
+            consumer_secret,
+            None,
+            None,
+            &[],
+            &[("oauth_callback", "oob")],
+        );
+
+        let response = client
+            .post(request_token_url)
+            .header(AUTHORIZATION, auth_header)
+            .send()
+            .await
+            .map_err(|err| format!("X OAuth1 request-token call failed: {}", err))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|err| format!("X OAuth1 request-token response read failed: {}", err))?;
+        if !status.is_success() {
+            return Err(format!("X OAuth1 request-token error: {} {}", status, body.trim()));
+        }
+
+        let fields = parse_form_encoded_body(&body);
+        let token = fields
+            .get("oauth_token")
+            .cloned()
+            .ok_or_else(|| "X OAuth1 request-token response missing oauth_token".to_string())?;
+        let secret = fields
+            .get("oauth_token_secret")
+            .cloned()
+            .ok_or_else(|| "X OAuth1 request-token response missing oauth_token_secret".to_string())?;
+
+        Ok(OAuth1RequestToken { token, secret })
+    }
+
+    /// Builds the URL the user opens to grant access and receive their PIN.
+    fn oauth1_authorize_url(authorize_url: &str, request_token: &str) -> String {
+        format!(
+            "{}?oauth_token={}",
+            authorize_url.trim_end_matches('/'),
+            percent_encode_rfc3986(request_token)
+        )
+    }
+
+    /// Exchanges a verified request token (plus the PIN the user read back)
+    /// for the long-lived access token/secret pair, the final leg of the
+    /// handshake.
+    async fn oauth1_access_token(
+        consumer_key: &str,
+        consumer_secret: &str,
+        request_token: &str,
+        request_token_secret: &str,
+        verifier: &str,
+        access_token_url: &str,
+    ) -> Result<(String, String), String> {
+        let client = reqwest::Client::new();
+        let auth_header = oauth1_authorization_header(
+            "POST",
+            access_token_url,
+            consumer_key,
+            consumer_secret,
+            Some(request_token),
+            Some(request_token_secret),
+            &[],
+            &[("oauth_verifier", verifier)],
+        );
+
+        let response = client
+            .post(access_token_url)
+            .header(AUTHORIZATION, auth_header)
+            .send()
+            .await
+            .map_err(|err| format!("X OAuth1 access-token call failed: {}", err))?;
+
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|err| format!("X OAuth1 access-token response read failed: {}", err))?;
+        if !status.is_success() {
+            return Err(format!("X OAuth1 access-token error: {} {}", status, body.trim()));
+        }
+
+        let fields = parse_form_encoded_body(&body);
+        let token = fields
+            .get("oauth_token")
+            .cloned()
+            .ok_or_else(|| "X OAuth1 access-token response missing oauth_token".to_string())?;
+        let secret = fields
+            .get("oauth_token_secret")
+            .cloned()
+            .ok_or_else(|| "X OAuth1 access-token response missing oauth_token_secret".to_string())?;
+
+        Ok((token, secret))
+    }
+
     async fn bearer_token(&self) -> Result<String, String> {
         match &self.auth {
             XApiAuth::Bearer(token) => Ok(token.clone()),
+            XApiAuth::OAuth1UserContext(_) => Err(
+                "this client holds OAuth 1.0a user-context credentials; use auth_header for signed requests instead of a bearer token"
+                    .to_string(),
+            ),
             XApiAuth::OAuthClientCredentials {
                 client_id,
                 client_secret,
@@ -269,6 +821,168 @@ impl XApiClient {
     }
 }
 
+type HmacSha1 = Hmac<Sha1>;
+
+/// Percent-encodes per RFC 3986 (the unreserved set is `A-Za-z0-9-._~`),
+/// which is stricter than `urlencoding`'s form-style escaping and is what
+/// OAuth 1.0a signature base strings require.
+fn percent_encode_rfc3986(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Parses an `application/x-www-form-urlencoded` response body (as returned
+/// by the OAuth 1.0a request-token and access-token endpoints) into a map.
+fn parse_form_encoded_body(body: &str) -> HashMap<String, String> {
+    body.trim()
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter_map(|pair| {
+            let mut parts = pair.splitn(2, '=');
+            let key = parts.next()?;
+            let value = parts.next().unwrap_or("");
+            Some((
+                urlencoding::decode(key).map(|v| v.into_owned()).unwrap_or_else(|_| key.to_string()),
+                urlencoding::decode(value).map(|v| v.into_owned()).unwrap_or_else(|_| value.to_string()),
+            ))
+        })
+        .collect()
+}
+
+/// Builds the `Authorization: OAuth ...` header value for a single request,
+/// per the OAuth 1.0a spec: a fresh nonce and timestamp are generated, the
+/// signature base string `METHOD&percentEncode(url)&percentEncode(sortedParams)`
+/// is assembled from the oauth_* fields plus any request params, and the
+/// whole thing is signed with HMAC-SHA1 using
+/// `percentEncode(consumer_secret)&percentEncode(token_secret)` as the key.
+#[allow(clippy::too_many_arguments)]
+fn oauth1_authorization_header(
+    method: &str,
+    url: &str,
+    consumer_key: &str,
+    consumer_secret: &str,
+    token: Option<&str>,
+    token_secret: Option<&str>,
+    request_params: &[(String, String)],
+    extra_oauth_params: &[(&str, &str)],
+) -> String {
+    let nonce = generate_nonce();
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        .to_string();
+
+    let mut oauth_params: Vec<(String, String)> = vec![
+        ("oauth_consumer_key".to_string(), consumer_key.to_string()),
+        ("oauth_nonce".to_string(), nonce),
+        ("oauth_signature_method".to_string(), "HMAC-SHA1".to_string()),
+        ("oauth_timestamp".to_string(), timestamp),
+        ("oauth_version".to_string(), "1.0".to_string()),
+    ];
+    if let Some(token) = token {
+        oauth_params.push(("oauth_token".to_string(), token.to_string()));
+    }
+    for (key, value) in extra_oauth_params {
+        oauth_params.push((key.to_string(), value.to_string()));
+    }
+
+    let mut signing_params = oauth_params.clone();
+    signing_params.extend(request_params.iter().cloned());
+    signing_params.sort();
+
+    let param_string = signing_params
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "{}={}",
+                percent_encode_rfc3986(key),
+                percent_encode_rfc3986(value)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let base_string = format!(
+        "{}&{}&{}",
+        method.to_uppercase(),
+        percent_encode_rfc3986(url),
+        percent_encode_rfc3986(&param_string)
+    );
+
+    let signing_key = format!(
+        "{}&{}",
+        percent_encode_rfc3986(consumer_secret),
+        percent_encode_rfc3986(token_secret.unwrap_or(""))
+    );
+
+    let mut mac = HmacSha1::new_from_slice(signing_key.as_bytes())
+        .expect("HMAC accepts a key of any length");
+    mac.update(base_string.as_bytes());
+    let signature = BASE64_STANDARD.encode(mac.finalize().into_bytes());
+
+    let mut header_params = oauth_params;
+    header_params.push(("oauth_signature".to_string(), signature));
+    header_params.sort();
+
+    let header_fields = header_params
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "{}=\"{}\"",
+                percent_encode_rfc3986(key),
+                percent_encode_rfc3986(value)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("OAuth {}", header_fields)
+}
+
+/// Generates a random, URL-safe nonce for the `oauth_nonce` field.
+fn generate_nonce() -> String {
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[async_trait::async_trait]
+impl crate::platform::SocialPlatform for XApiClient {
+    async fn fetch_user(&self, handle: &str) -> Result<crate::platform::PlatformProfile, String> {
+        let profile = self.fetch_user_by_username(handle).await?;
+        Ok(crate::platform::PlatformProfile {
+            id: profile.id,
+            followers: profile.followers,
+            following: profile.following,
+            posts_count: None,
+            created_at: profile.created_at,
+            verified: profile.verified,
+        })
+    }
+
+    async fn fetch_post_metrics(
+        &self,
+        post_id: &str,
+    ) -> Result<crate::platform::PlatformPostMetrics, String> {
+        let metrics = self.fetch_tweet_metrics(post_id).await?;
+        Ok(crate::platform::PlatformPostMetrics {
+            likes: metrics.like_count,
+            reposts: metrics.retweet_count,
+            replies: metrics.reply_count,
+            quotes: Some(metrics.quote_count),
+        })
+    }
+}
+
 fn decode_bearer(value: String) -> String {
     if value.contains('%') {
         match urlencoding::decode(&value) {
@@ -321,8 +1035,8 @@ struct XUserResponse {
 }
 
 #[derive(Deserialize)]
-struct XUser {
-    id: String,
+pub(crate) struct XUser {
+    pub(crate) id: String,
     username: String,
     name: String,
     created_at: Option<String>,
@@ -342,3 +1056,22 @@ struct OAuthTokenResponse {
     access_token: String,
     expires_in: Option<u64>,
 }
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TweetMetrics {
+    pub like_count: u64,
+    pub reply_count: u64,
+    pub retweet_count: u64,
+    pub quote_count: u64,
+    pub impression_count: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct TweetResponse {
+    data: Option<Tweet>,
+}
+
+#[derive(Deserialize)]
+struct Tweet {
+    public_metrics: Option<TweetMetrics>,
+}
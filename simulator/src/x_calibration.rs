@@ -0,0 +1,274 @@
+use reqwest::header::AUTHORIZATION;
+use serde::Deserialize;
+
+use crate::x_api::{XApiClient, XUserProfile};
+use virality_sim::calibration::CalibrationSample;
+
+/// Pulls a user's recent posts and their real engagement counts, turning each
+/// into a ready-to-use `CalibrationSample`. Prefers the official API when the
+/// client has credentials, and falls back to scraping public post pages
+/// (Nitter-style) when none are configured.
+pub async fn build_calibration_corpus(
+    client: Option<&XApiClient>,
+    handle: &str,
+    max_posts: usize,
+) -> Result<Vec<CalibrationSample>, String> {
+    if let Some(client) = client {
+        return fetch_via_api(client, handle, max_posts).await;
+    }
+    fetch_via_scrape(handle, max_posts).await
+}
+
+async fn fetch_via_api(
+    client: &XApiClient,
+    handle: &str,
+    max_posts: usize,
+) -> Result<Vec<CalibrationSample>, String> {
+    let profile = client.fetch_user_by_username(handle).await?;
+    let posts = client.fetch_recent_posts(&profile.id, max_posts).await?;
+    posts
+        .into_iter()
+        .map(|post| to_sample(&profile, post))
+        .collect()
+}
+
+fn to_sample(profile: &XUserProfile, post: RecentPost) -> Result<CalibrationSample, String> {
+    let mut sample = CalibrationSample {
+        post_id: post.id.try_into()?,
+        post_text: post.text,
+        author_followers: profile.followers,
+        author_following: Some(profile.following),
+        account_age_days: profile.created_at.as_deref().map(account_age_from_iso),
+        avg_engagement_rate: None,
+        posts_per_day: None,
+        verified: profile.verified,
+        media_type: "none".to_string(),
+        actual_impressions: post.metrics.impression_count.unwrap_or(0),
+        actual_likes: post.metrics.like_count,
+        actual_replies: post.metrics.reply_count,
+        actual_reposts: post.metrics.retweet_count,
+        actual_quotes: post.metrics.quote_count,
+        actual_shares: None,
+    };
+    if sample.actual_impressions == 0 {
+        // Recent-tweet payloads without elevated access omit impressions;
+        // approximate with engagement volume so the ratio metrics still work.
+        sample.actual_impressions =
+            (sample.actual_likes + sample.actual_replies + sample.actual_reposts).max(1) * 20;
+    }
+    Ok(sample)
+}
+
+fn account_age_from_iso(created_at: &str) -> u32 {
+    // created_at is RFC3339 (e.g. 2012-03-05T12:00:00.000Z); approximate the
+    // account age in days from the year/month/day prefix without pulling in a
+    // full datetime dependency.
+    let digits: String = created_at.chars().take(10).collect();
+    let parts: Vec<&str> = digits.split('-').collect();
+    if parts.len() != 3 {
+        return 0;
+    }
+    let (year, month, day) = (
+        parts[0].parse::<i64>().unwrap_or(1970),
+        parts[1].parse::<i64>().unwrap_or(1),
+        parts[2].parse::<i64>().unwrap_or(1),
+    );
+    let days_since_epoch = days_from_civil(year, month, day);
+    let now_days = days_from_civil(1970, 1, 1) + (now_unix_days());
+    (now_days - days_since_epoch).max(0) as u32
+}
+
+fn now_unix_days() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| (d.as_secs() / 86_400) as i64)
+        .unwrap_or(0)
+}
+
+/// Howard Hinnant's days-from-civil algorithm (proleptic Gregorian).
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = (y - era * 400) as i64;
+    let mp = (m + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+struct RecentPost {
+    id: String,
+    text: String,
+    metrics: RecentPostMetrics,
+}
+
+#[derive(Deserialize)]
+struct RecentPostMetrics {
+    like_count: u64,
+    reply_count: u64,
+    retweet_count: u64,
+    quote_count: Option<u64>,
+    impression_count: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct TimelineResponse {
+    data: Option<Vec<TimelinePost>>,
+}
+
+#[derive(Deserialize)]
+struct TimelinePost {
+    id: String,
+    text: String,
+    public_metrics: Option<RecentPostMetrics>,
+}
+
+impl XApiClient {
+    pub async fn fetch_recent_posts(
+        &self,
+        user_id: &str,
+        max_results: usize,
+    ) -> Result<Vec<RecentPost>, String> {
+        let token = self.bearer_token_for_calibration().await?;
+        let response = self
+            .http_client()
+            .get(format!(
+                "{}/users/{}/tweets",
+                self.api_base().trim_end_matches('/'),
+                user_id
+            ))
+            .query(&[
+                ("max_results", max_results.clamp(5, 100).to_string()),
+                ("tweet.fields".to_string(), "public_metrics".to_string()),
+            ])
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .send()
+            .await
+            .map_err(|err| format!("X API request failed: {}", err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("X API error: {} {}", status, body.trim()));
+        }
+
+        let body: TimelineResponse = response
+            .json()
+            .await
+            .map_err(|err| format!("X API response parse failed: {}", err))?;
+
+        Ok(body
+            .data
+            .unwrap_or_default()
+            .into_iter()
+            .map(|post| RecentPost {
+                id: post.id,
+                text: post.text,
+                metrics: post.public_metrics.unwrap_or(RecentPostMetrics {
+                    like_count: 0,
+                    reply_count: 0,
+                    retweet_count: 0,
+                    quote_count: None,
+                    impression_count: None,
+                }),
+            })
+            .collect())
+    }
+}
+
+async fn fetch_via_scrape(handle: &str, max_posts: usize) -> Result<Vec<CalibrationSample>, String> {
+    let base = std::env::var("NITTER_BASE").unwrap_or_else(|_| "https://nitter.net".to_string());
+    let url = format!("{}/{}", base.trim_end_matches('/'), handle.trim_start_matches('@'));
+
+    let client = reqwest::Client::new();
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|err| format!("scrape request failed: {}", err))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("scrape error: {}", status));
+    }
+
+    let html = response
+        .text()
+        .await
+        .map_err(|err| format!("scrape body read failed: {}", err))?;
+
+    Ok(parse_nitter_timeline(&html, handle, max_posts))
+}
+
+/// Extracts per-post engagement counts out of Nitter's timeline markup.
+/// Nitter renders each stat as `<span class="icon-{kind}"></span> N` inside a
+/// `tweet-stats` block, so we scan for those anchors rather than depending on
+/// a full HTML parser.
+fn parse_nitter_timeline(html: &str, handle: &str, max_posts: usize) -> Vec<CalibrationSample> {
+    let mut samples = Vec::new();
+    for (idx, block) in html.split("tweet-body").skip(1).enumerate() {
+        if samples.len() >= max_posts {
+            break;
+        }
+        let text = extract_between(block, "tweet-content", "</div>").unwrap_or_default();
+        let likes = extract_stat(block, "icon-heart");
+        let replies = extract_stat(block, "icon-comment");
+        let reposts = extract_stat(block, "icon-retweet");
+        let quotes = extract_stat(block, "icon-quote");
+
+        samples.push(CalibrationSample {
+            post_id: format!("{}_{}", handle.trim_start_matches('@'), idx)
+                .try_into()
+                .expect("scraped post id is always non-empty"),
+            post_text: text,
+            author_followers: 0,
+            author_following: None,
+            account_age_days: None,
+            avg_engagement_rate: None,
+            posts_per_day: None,
+            verified: None,
+            media_type: "none".to_string(),
+            actual_impressions: ((likes + replies + reposts + quotes).max(1)) * 20,
+            actual_likes: likes,
+            actual_replies: replies,
+            actual_reposts: reposts,
+            actual_quotes: Some(quotes),
+            actual_shares: None,
+        });
+    }
+    samples
+}
+
+fn extract_between(haystack: &str, start_needle: &str, end_needle: &str) -> Option<String> {
+    let start = haystack.find(start_needle)? + start_needle.len();
+    let rest = &haystack[start..];
+    let end = rest.find(end_needle)?;
+    Some(strip_tags(&rest[..end]))
+}
+
+fn strip_tags(value: &str) -> String {
+    let mut out = String::new();
+    let mut in_tag = false;
+    for ch in value.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => out.push(ch),
+            _ => {}
+        }
+    }
+    out.trim().to_string()
+}
+
+fn extract_stat(block: &str, icon_class: &str) -> u64 {
+    let Some(idx) = block.find(icon_class) else {
+        return 0;
+    };
+    let tail = &block[idx..];
+    tail.chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>()
+        .parse()
+        .unwrap_or(0)
+}
@@ -0,0 +1,159 @@
+use reqwest::header::AUTHORIZATION;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+
+use crate::x_api::{TweetMetrics, XApiClient, XUser, XUserProfile};
+use virality_sim::scoring::ScoredCandidate;
+
+/// X's bulk lookup endpoints accept at most 100 ids per request.
+const MAX_BATCH_SIZE: usize = 100;
+
+impl XApiClient {
+    /// Bulk-fetches users by id via `GET /users?ids=...`, chunking at the
+    /// API's 100-id batch limit and reusing the existing token caching, and
+    /// returns a map keyed by user id so callers can hydrate many candidates
+    /// without one round-trip per author.
+    pub async fn fetch_users_by_ids(
+        &self,
+        ids: &[String],
+    ) -> Result<HashMap<String, XUserProfile>, String> {
+        let token = self.bearer_token_for_calibration().await?;
+        let mut out = HashMap::new();
+
+        for chunk in dedup(ids).chunks(MAX_BATCH_SIZE) {
+            let response = self
+                .http_client()
+                .get(format!("{}/users", self.api_base().trim_end_matches('/')))
+                .query(&[
+                    ("ids".to_string(), chunk.join(",")),
+                    (
+                        "user.fields".to_string(),
+                        "public_metrics,created_at,verified,protected".to_string(),
+                    ),
+                ])
+                .header(AUTHORIZATION, format!("Bearer {}", token))
+                .send()
+                .await
+                .map_err(|err| format!("X API request failed: {}", err))?;
+
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                return Err(format!("X API error: {} {}", status, body.trim()));
+            }
+
+            let body: UsersResponse = response
+                .json()
+                .await
+                .map_err(|err| format!("X API response parse failed: {}", err))?;
+
+            for user in body.data.unwrap_or_default() {
+                out.insert(user.id.clone(), XUserProfile::from(user));
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Bulk-fetches tweets by id via `GET /tweets?ids=...`, with the same
+    /// chunking and batching behavior as `fetch_users_by_ids`.
+    pub async fn fetch_tweets_by_ids(
+        &self,
+        ids: &[String],
+    ) -> Result<HashMap<String, TweetMetrics>, String> {
+        let token = self.bearer_token_for_calibration().await?;
+        let mut out = HashMap::new();
+
+        for chunk in dedup(ids).chunks(MAX_BATCH_SIZE) {
+            let response = self
+                .http_client()
+                .get(format!("{}/tweets", self.api_base().trim_end_matches('/')))
+                .query(&[
+                    ("ids".to_string(), chunk.join(",")),
+                    ("tweet.fields".to_string(), "public_metrics".to_string()),
+                ])
+                .header(AUTHORIZATION, format!("Bearer {}", token))
+                .send()
+                .await
+                .map_err(|err| format!("X API request failed: {}", err))?;
+
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                return Err(format!("X API error: {} {}", status, body.trim()));
+            }
+
+            let body: TweetsResponse = response
+                .json()
+                .await
+                .map_err(|err| format!("X API response parse failed: {}", err))?;
+
+            for tweet in body.data.unwrap_or_default() {
+                if let Some(metrics) = tweet.public_metrics {
+                    out.insert(tweet.id, metrics);
+                }
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+/// Fills each `ScoredCandidate`'s author/post-derived fields from the bulk
+/// lookup maps in a handful of round-trips, so ranking a feed of hundreds of
+/// candidates doesn't require one author fetch and one post fetch per
+/// candidate.
+pub async fn hydrate_candidates(
+    client: &XApiClient,
+    candidates: &mut [ScoredCandidate],
+) -> Result<(), String> {
+    let author_ids: Vec<String> = candidates
+        .iter()
+        .map(|candidate| candidate.author_id.clone())
+        .collect();
+    let post_ids: Vec<String> = candidates
+        .iter()
+        .map(|candidate| candidate.post_id.clone())
+        .collect();
+
+    let (users, tweets) = tokio::try_join!(
+        client.fetch_users_by_ids(&author_ids),
+        client.fetch_tweets_by_ids(&post_ids),
+    )?;
+
+    for candidate in candidates.iter_mut() {
+        if let Some(user) = users.get(&candidate.author_id) {
+            candidate.author_followers = user.followers;
+            candidate.author_verified = user.verified;
+        }
+        if let Some(metrics) = tweets.get(&candidate.post_id) {
+            candidate.post_impressions = metrics.impression_count;
+        }
+    }
+
+    Ok(())
+}
+
+fn dedup(ids: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    ids.iter()
+        .filter(|id| seen.insert((*id).clone()))
+        .cloned()
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct UsersResponse {
+    data: Option<Vec<XUser>>,
+}
+
+#[derive(Deserialize)]
+struct TweetsResponse {
+    data: Option<Vec<BulkTweet>>,
+}
+
+#[derive(Deserialize)]
+struct BulkTweet {
+    id: String,
+    public_metrics: Option<TweetMetrics>,
+}
@@ -0,0 +1,137 @@
+use reqwest::header::AUTHORIZATION;
+use serde::Deserialize;
+use std::env;
+use tokio_stream::StreamExt;
+
+/// A minimal client for X's filtered/user streaming endpoints, parallel to
+/// `XApiClient` but built around a single long-lived chunked response instead
+/// of one-shot requests. The stream's rules (which posts to match) are
+/// assumed to already be configured server-side; this client only consumes
+/// the resulting newline-delimited JSON body.
+#[derive(Clone)]
+pub struct XStreamClient {
+    client: reqwest::Client,
+    api_base: String,
+    bearer_token: String,
+}
+
+/// One observed snapshot of a post's public engagement counts, as emitted by
+/// the stream.
+#[derive(Debug, Clone)]
+pub struct StreamRecord {
+    pub post_id: String,
+    pub like_count: u64,
+    pub reply_count: u64,
+    pub repost_count: u64,
+    pub quote_count: u64,
+    pub impression_count: Option<u64>,
+}
+
+impl XStreamClient {
+    pub fn from_env() -> Option<Self> {
+        let bearer_token = env::var("X_API_BEARER_TOKEN").ok()?;
+        let api_base = env::var("X_API_STREAM_BASE")
+            .unwrap_or_else(|_| "https://api.twitter.com/2".to_string());
+        Some(Self {
+            client: reqwest::Client::new(),
+            api_base,
+            bearer_token,
+        })
+    }
+
+    pub fn new(api_base: String, bearer_token: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_base,
+            bearer_token,
+        }
+    }
+
+    /// Opens the filtered stream and invokes `on_record` for each post
+    /// snapshot until either the connection ends or `max_records` have been
+    /// delivered. The body is newline-delimited JSON, so each chunk is
+    /// buffered until a full line is available before being parsed; X sends
+    /// blank keep-alive lines roughly every 20 seconds, which are skipped.
+    pub async fn consume_filtered_stream<F>(
+        &self,
+        max_records: usize,
+        mut on_record: F,
+    ) -> Result<(), String>
+    where
+        F: FnMut(StreamRecord) + Send,
+    {
+        let url = format!("{}/tweets/search/stream", self.api_base.trim_end_matches('/'));
+        let response = self
+            .client
+            .get(&url)
+            .query(&[("tweet.fields", "public_metrics")])
+            .header(AUTHORIZATION, format!("Bearer {}", self.bearer_token))
+            .send()
+            .await
+            .map_err(|err| format!("X stream request failed: {}", err))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("X stream error: {} {}", status, body.trim()));
+        }
+
+        let mut stream = response.bytes_stream();
+        let mut buffer = String::new();
+        let mut delivered = 0;
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|err| format!("X stream read failed: {}", err))?;
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+
+            while let Some(newline) = buffer.find('\n') {
+                let line = buffer[..newline].trim().to_string();
+                buffer.drain(..=newline);
+
+                if line.is_empty() {
+                    continue;
+                }
+
+                let envelope: StreamEnvelope = serde_json::from_str(&line)
+                    .map_err(|err| format!("X stream parse failed: {}", err))?;
+                if let Some(post) = envelope.data {
+                    let metrics = post.public_metrics.unwrap_or_default();
+                    on_record(StreamRecord {
+                        post_id: post.id,
+                        like_count: metrics.like_count,
+                        reply_count: metrics.reply_count,
+                        repost_count: metrics.retweet_count,
+                        quote_count: metrics.quote_count.unwrap_or(0),
+                        impression_count: metrics.impression_count,
+                    });
+                    delivered += 1;
+                    if delivered >= max_records {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct StreamEnvelope {
+    data: Option<StreamPost>,
+}
+
+#[derive(Deserialize)]
+struct StreamPost {
+    id: String,
+    public_metrics: Option<StreamMetrics>,
+}
+
+#[derive(Deserialize, Default)]
+struct StreamMetrics {
+    like_count: u64,
+    reply_count: u64,
+    retweet_count: u64,
+    quote_count: Option<u64>,
+    impression_count: Option<u64>,
+}
@@ -0,0 +1,18 @@
+use virality_sim::calibration::{ConformanceRunner, ConformanceVector};
+use virality_sim::config::ScoringConfig;
+
+#[test]
+fn scoring_pipeline_matches_golden_vectors() {
+    let raw = include_str!("fixtures/conformance_vectors.json");
+    let vectors: Vec<ConformanceVector> =
+        serde_json::from_str(raw).expect("fixture should parse as conformance vectors");
+
+    let runner = ConformanceRunner::new(vectors);
+    let report = runner.run(&ScoringConfig::default());
+
+    assert!(
+        report.is_conformant(),
+        "scoring pipeline diverged from golden vectors: {:#?}",
+        report.mismatches
+    );
+}
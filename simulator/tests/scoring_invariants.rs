@@ -0,0 +1,195 @@
+//! Property-based invariants for `ScoringPipeline` and its sub-scorers,
+//! complementing the example-based cases in `scoring_pipeline.rs`.
+
+use proptest::prelude::*;
+
+use virality_sim::scoring::{
+    ActionWeights, AuthorDiversityConfig, AuthorDiversityScorer, OonScorer, OonScorerConfig,
+    ScoredCandidate, ScoringPipeline, WeightedScorer,
+};
+use virality_sim::ActionProbs;
+
+const AUTHOR_IDS: &[&str] = &["author_a", "author_b", "author_c"];
+
+fn unit_prob() -> impl Strategy<Value = f64> {
+    0.0f64..=1.0
+}
+
+fn action_probs_strategy() -> impl Strategy<Value = ActionProbs> {
+    (
+        (unit_prob(), unit_prob(), unit_prob(), unit_prob(), unit_prob()),
+        (unit_prob(), unit_prob(), unit_prob(), unit_prob(), unit_prob()),
+        (unit_prob(), unit_prob(), unit_prob(), unit_prob(), unit_prob()),
+        (unit_prob(), unit_prob(), unit_prob(), unit_prob(), 0.0f64..=120.0),
+    )
+        .prop_map(|(a, b, c, d)| {
+            let (like, reply, repost, quote, click) = a;
+            let (profile_click, video_view, photo_expand, share, share_dm) = b;
+            let (share_link, dwell, follow_author, quoted_click, not_interested) = c;
+            let (block, mute, report, hide_post, dwell_time) = d;
+            ActionProbs {
+                like,
+                reply,
+                repost,
+                quote,
+                click,
+                profile_click,
+                video_view,
+                photo_expand,
+                share,
+                share_dm,
+                share_link,
+                dwell,
+                follow_author,
+                quoted_click,
+                not_interested,
+                block,
+                mute,
+                report,
+                hide_post,
+                dwell_time,
+            }
+        })
+}
+
+fn candidate_strategy() -> impl Strategy<Value = (&'static str, bool, Option<f64>, ActionProbs)> {
+    (
+        prop::sample::select(AUTHOR_IDS),
+        any::<bool>(),
+        prop::option::of(0.0f64..=20.0),
+        action_probs_strategy(),
+    )
+}
+
+proptest! {
+    /// Candidates come back ordered by non-increasing final score, no matter
+    /// how many candidates share an author or how their actions are spread.
+    #[test]
+    fn pipeline_orders_candidates_by_non_increasing_score(
+        inputs in prop::collection::vec(candidate_strategy(), 2..8)
+    ) {
+        let pipeline = ScoringPipeline::new(
+            WeightedScorer::new(ActionWeights::default(), 6.0, 1.0),
+            AuthorDiversityScorer::new(AuthorDiversityConfig::default()),
+            OonScorer::new(OonScorerConfig::default()),
+        );
+
+        let mut candidates: Vec<ScoredCandidate> = inputs
+            .into_iter()
+            .enumerate()
+            .map(|(index, (author_id, is_oon, vqv_duration, actions))| {
+                ScoredCandidate::new(format!("post{index}"), author_id.to_string(), is_oon, vqv_duration, actions)
+            })
+            .collect();
+
+        pipeline.score(&mut candidates);
+
+        for pair in candidates.windows(2) {
+            prop_assert!(pair[0].score >= pair[1].score);
+        }
+    }
+
+    /// `diversity_multiplier` always lies in `[floor, 1.0]`, and repeating the
+    /// same author strictly decays it occurrence over occurrence (until the
+    /// floor is reached, where it levels off rather than going lower).
+    #[test]
+    fn diversity_multiplier_stays_bounded_and_decays_per_author(
+        decay in 0.01f64..0.99,
+        floor in 0.0f64..0.5,
+        repeats in 2usize..6,
+    ) {
+        let scorer = AuthorDiversityScorer::new(AuthorDiversityConfig { decay, floor });
+        let mut candidates: Vec<ScoredCandidate> = (0..repeats)
+            .map(|index| {
+                let mut candidate = ScoredCandidate::new(
+                    format!("post{index}"),
+                    "same_author".to_string(),
+                    false,
+                    None,
+                    ActionProbs::default(),
+                );
+                candidate.weighted_score = 1.0;
+                candidate
+            })
+            .collect();
+
+        scorer.score(&mut candidates);
+
+        let mut previous = f64::INFINITY;
+        for candidate in &candidates {
+            prop_assert!(candidate.diversity_multiplier >= floor - 1e-9);
+            prop_assert!(candidate.diversity_multiplier <= 1.0 + 1e-9);
+            prop_assert!(candidate.diversity_multiplier <= previous + 1e-9);
+            previous = candidate.diversity_multiplier;
+        }
+    }
+
+    /// `oon_multiplier` is exactly the configured multiplier for an
+    /// out-of-network candidate, and exactly `1.0` otherwise.
+    #[test]
+    fn oon_multiplier_matches_flag(
+        multiplier in 0.0f64..1.0,
+        is_oon in any::<bool>(),
+        starting_score in -10.0f64..10.0,
+    ) {
+        let scorer = OonScorer::new(OonScorerConfig { multiplier });
+        let mut candidate = ScoredCandidate::new(
+            "post".to_string(),
+            "author".to_string(),
+            is_oon,
+            None,
+            ActionProbs::default(),
+        );
+        candidate.score = starting_score;
+
+        scorer.score(&mut candidate, is_oon);
+
+        if is_oon {
+            prop_assert!((candidate.oon_multiplier - multiplier).abs() < 1e-9);
+            prop_assert!((candidate.score - starting_score * multiplier).abs() < 1e-9);
+        } else {
+            prop_assert!((candidate.oon_multiplier - 1.0).abs() < 1e-9);
+            prop_assert!((candidate.score - starting_score).abs() < 1e-9);
+        }
+    }
+
+    /// When `score_offset` is at least as large as the magnitude of the most
+    /// negative `ActionWeights` field (`report`), a single negatively-weighted
+    /// action can never drive `WeightedScorer::score` below zero.
+    #[test]
+    fn score_offset_keeps_single_action_scores_non_negative_when_dominant(
+        field in 0usize..19,
+        probability in unit_prob(),
+        score_offset in 6.0f64..20.0,
+    ) {
+        let mut actions = ActionProbs::default();
+        match field {
+            0 => actions.like = probability,
+            1 => actions.reply = probability,
+            2 => actions.repost = probability,
+            3 => actions.quote = probability,
+            4 => actions.click = probability,
+            5 => actions.profile_click = probability,
+            6 => actions.video_view = probability,
+            7 => actions.photo_expand = probability,
+            8 => actions.share = probability,
+            9 => actions.share_dm = probability,
+            10 => actions.share_link = probability,
+            11 => actions.dwell = probability,
+            12 => actions.follow_author = probability,
+            13 => actions.quoted_click = probability,
+            14 => actions.not_interested = probability,
+            15 => actions.block = probability,
+            16 => actions.mute = probability,
+            17 => actions.report = probability,
+            18 => actions.hide_post = probability,
+            _ => unreachable!(),
+        }
+
+        let scorer = WeightedScorer::new(ActionWeights::default(), 6.0, score_offset);
+        let score = scorer.score(&actions, None);
+
+        prop_assert!(score >= -1e-9);
+    }
+}
+
@@ -21,6 +21,7 @@ fn empty_actions() -> ActionProbs {
         block: 0.0,
         mute: 0.0,
         report: 0.0,
+        hide_post: 0.0,
         dwell_time: 0.0,
     }
 }